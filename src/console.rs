@@ -0,0 +1,368 @@
+use std::mem::take;
+
+use macroquad::{
+    input::{get_char_pressed, is_key_pressed, KeyCode},
+    math::vec2,
+};
+use serde_json::{from_value, Value};
+
+use crate::{
+    constants::{
+        CONSOLE_HELP_TEXT, CONSOLE_KEY, CONSOLE_MAX_LINES, MOVE_SPEED, PLAYER_MAX_HEALTH,
+        SPRINT_SPEED_MODIFIER,
+    },
+    model::{enemy::EnemyType, GameEvent, GameObjects},
+};
+
+/// Values from `constants.rs` that can be changed while the game runs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cvars {
+    /// Multiplies the time the game advances each frame
+    pub timescale: f32,
+    pub move_speed: f32,
+    pub sprint_speed_modifier: f32,
+    /// The player's health stays full
+    pub god: bool,
+    /// The player walks through walls
+    pub noclip: bool,
+    /// Draws the top-down debug map over the game
+    pub debug_map: bool,
+}
+impl Default for Cvars {
+    fn default() -> Self {
+        Self {
+            timescale: 1.0,
+            move_speed: MOVE_SPEED,
+            sprint_speed_modifier: SPRINT_SPEED_MODIFIER,
+            god: false,
+            noclip: false,
+            debug_map: false,
+        }
+    }
+}
+impl Cvars {
+    const NAMES: [&'static str; 3] = ["timescale", "move_speed", "sprint_speed_modifier"];
+
+    fn get_mut(&mut self, name: &str) -> Option<&mut f32> {
+        match name {
+            "timescale" => Some(&mut self.timescale),
+            "move_speed" => Some(&mut self.move_speed),
+            "sprint_speed_modifier" => Some(&mut self.sprint_speed_modifier),
+            _ => None,
+        }
+    }
+
+    /// Whether any cvar that changes the game is off its default, the debug map only shows it
+    fn is_cheating(&self) -> bool {
+        Cvars {
+            debug_map: false,
+            ..*self
+        } != Cvars::default()
+    }
+
+    /// Timescale has to stay above zero and speeds can't go negative
+    fn get_usage(name: &str, value: f32) -> Option<String> {
+        let is_valid = value.is_finite()
+            && match name {
+                "timescale" => value > 0.0,
+                _ => value >= 0.0,
+            };
+        let range = match name {
+            "timescale" => "greater than 0",
+            _ => "0 or greater",
+        };
+
+        (!is_valid).then(|| format!("Usage: {} <number {}>", name, range))
+    }
+}
+
+/// Requests the game loop carries out, because the console can't change levels itself
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConsoleAction {
+    Warp(u32),
+}
+
+/// Drop-down developer console, commands run against `GameObjects` without needing a window
+#[derive(Debug, Default)]
+pub struct Console {
+    pub is_open: bool,
+    pub input: String,
+    /// Commands entered and their output, newest last
+    pub lines: Vec<String>,
+    pub cvars: Cvars,
+    /// Set by commands that change the game, cleared once the run has been marked
+    pub has_cheated: bool,
+}
+impl Console {
+    pub fn print(&mut self, text: impl Into<String>) {
+        self.lines.push(text.into());
+        let overflow = self.lines.len().saturating_sub(CONSOLE_MAX_LINES);
+        self.lines.drain(..overflow);
+    }
+
+    /// Toggles the console and collects typed text, returns a line once Enter is pressed
+    pub fn handle_keys(&mut self) -> Option<String> {
+        if is_key_pressed(CONSOLE_KEY) {
+            self.is_open = !self.is_open;
+        }
+        // Characters typed while closed, including the toggle key, are dropped
+        while let Some(character) = get_char_pressed() {
+            if self.is_open && !character.is_control() && character != '`' && character != '~' {
+                self.input.push(character);
+            }
+        }
+        if !self.is_open {
+            return None;
+        }
+
+        if is_key_pressed(KeyCode::Backspace) {
+            self.input.pop();
+        }
+        if is_key_pressed(KeyCode::Enter) {
+            return Some(take(&mut self.input));
+        }
+
+        None
+    }
+
+    /// Runs every command of a script separated by newlines or semicolons
+    pub fn execute_all(
+        &mut self,
+        script: &str,
+        game_objects: &mut GameObjects,
+    ) -> (Vec<GameEvent>, Vec<ConsoleAction>) {
+        script
+            .split(['\n', ';'])
+            .map(|line| self.execute(line, game_objects))
+            .fold(
+                (vec![], vec![]),
+                |(mut events, mut actions), (new_events, action)| {
+                    events.extend(new_events);
+                    actions.extend(action);
+                    (events, actions)
+                },
+            )
+    }
+
+    pub fn execute(
+        &mut self,
+        line: &str,
+        game_objects: &mut GameObjects,
+    ) -> (Vec<GameEvent>, Option<ConsoleAction>) {
+        let words: Vec<_> = line.split_whitespace().collect();
+        let Some((command, args)) = words.split_first() else {
+            return (vec![], None);
+        };
+        self.print(format!("> {}", line.trim()));
+
+        match (*command, args) {
+            ("help", []) => self.print(CONSOLE_HELP_TEXT),
+            ("god", []) => {
+                self.has_cheated = true;
+                self.cvars.god = !self.cvars.god;
+                self.print(format!("God mode {}", on_off(self.cvars.god)));
+            }
+            ("noclip", []) => {
+                self.has_cheated = true;
+                self.cvars.noclip = !self.cvars.noclip;
+                self.print(format!("Noclip {}", on_off(self.cvars.noclip)));
+            }
+            ("debug_map", []) => {
+                self.cvars.debug_map = !self.cvars.debug_map;
+                self.print(format!("Debug map {}", on_off(self.cvars.debug_map)));
+            }
+            ("give", ["ammo"]) => {
+                self.has_cheated = true;
+                game_objects.player_info.bullets = game_objects.player_info.max_bullets;
+                self.print("Ammo refilled");
+            }
+            ("give", ["health"]) => {
+                self.has_cheated = true;
+                game_objects.player_info.health = PLAYER_MAX_HEALTH;
+                self.print("Health refilled");
+            }
+            ("warp", [level]) => match level.parse() {
+                Ok(level) => {
+                    self.has_cheated = true;
+                    return (vec![], Some(ConsoleAction::Warp(level)));
+                }
+                Err(_) => self.print(format!("Not a level number: {}", level)),
+            },
+            ("kill_all", []) => return (self.kill_all(game_objects), None),
+            ("spawn", [enemy_type, x, y]) => return (self.spawn(enemy_type, x, y), None),
+            ("cvars", []) => {
+                for name in Cvars::NAMES {
+                    let value = *self.cvars.get_mut(name).unwrap();
+                    self.print(format!("{} = {}", name, value));
+                }
+            }
+            (name, args) => self.set_cvar(name, args),
+        }
+
+        (vec![], None)
+    }
+
+    fn kill_all(&mut self, game_objects: &mut GameObjects) -> Vec<GameEvent> {
+        self.has_cheated = true;
+        let events: Vec<_> = game_objects
+            .enemies
+            .drain(..)
+            .map(|enemy| GameEvent::EnemyKilled {
+                position: enemy.entity.position,
+                enemy_type: enemy.enemy_type,
+                name: enemy.name,
+            })
+            .collect();
+        self.print(format!("Killed {} enemies", events.len()));
+        events
+    }
+
+    fn spawn(&mut self, enemy_type: &str, x: &str, y: &str) -> Vec<GameEvent> {
+        let enemy_type: Result<EnemyType, _> = from_value(Value::String(enemy_type.to_string()));
+        match (enemy_type, x.parse(), y.parse()) {
+            (Ok(enemy_type), Ok(x), Ok(y)) => {
+                self.has_cheated = true;
+                self.print(format!("Spawned {:?} at {} {}", enemy_type, x, y));
+                vec![GameEvent::SpawnEnemy {
+                    position: vec2(x, y),
+                    enemy_type,
+                }]
+            }
+            _ => {
+                self.print("Usage: spawn <enemy> <x> <y>");
+                vec![]
+            }
+        }
+    }
+
+    fn set_cvar(&mut self, name: &str, args: &[&str]) {
+        let Some(cvar) = self.cvars.get_mut(name) else {
+            self.print(format!("Unknown command: {} (try help)", name));
+            return;
+        };
+
+        let text = match args {
+            [] => format!("{} = {}", name, cvar),
+            [value] => match value.parse() {
+                Ok(value) => match Cvars::get_usage(name, value) {
+                    Some(usage) => usage,
+                    None => {
+                        *cvar = value;
+                        self.has_cheated = true;
+                        format!("{} = {}", name, value)
+                    }
+                },
+                Err(_) => format!("Not a number: {}", value),
+            },
+            _ => format!("Usage: {} [value]", name),
+        };
+        self.print(text);
+    }
+
+    /// Whether a cheat was used since the last call or is still switched on
+    pub fn take_cheated(&mut self) -> bool {
+        take(&mut self.has_cheated) || self.cvars.is_cheating()
+    }
+
+    /// Cheats that act every frame rather than once when entered
+    pub fn apply_cheats(&self, game_objects: &mut GameObjects) {
+        if self.cvars.god {
+            game_objects.player_info.health = PLAYER_MAX_HEALTH;
+        }
+    }
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{difficulty::Difficulty, PlayerInfo};
+
+    use super::*;
+
+    #[test]
+    fn test_execute_commands() {
+        let mut console = Console::default();
+        let mut game_objects = GameObjects {
+            player_info: PlayerInfo {
+                bullets: 0,
+                ..Default::default()
+            },
+            enemies: vec![EnemyType::Melee.to_enemy(vec2(1.0, 2.0), Difficulty::Normal)],
+            ..Default::default()
+        };
+
+        let (events, actions) = console.execute_all(
+            "god; give ammo; timescale 0.5\nkill_all; spawn Ranged 3 4; warp 2",
+            &mut game_objects,
+        );
+
+        assert!(console.cvars.god);
+        assert_eq!(console.cvars.timescale, 0.5);
+        assert!(console.take_cheated());
+        console.execute_all("god; timescale 1", &mut game_objects);
+        assert!(console.take_cheated());
+        assert!(!console.take_cheated());
+        assert_eq!(
+            game_objects.player_info.bullets,
+            game_objects.player_info.max_bullets
+        );
+        assert!(game_objects.enemies.is_empty());
+        assert_eq!(
+            events,
+            vec![
+                GameEvent::EnemyKilled {
+                    position: vec2(1.0, 2.0),
+                    enemy_type: EnemyType::Melee,
+                    name: None,
+                },
+                GameEvent::SpawnEnemy {
+                    position: vec2(3.0, 4.0),
+                    enemy_type: EnemyType::Ranged,
+                },
+            ]
+        );
+        assert_eq!(actions, vec![ConsoleAction::Warp(2)]);
+    }
+
+    #[test]
+    fn test_invalid_commands() {
+        let mut console = Console::default();
+        let mut game_objects = GameObjects::default();
+
+        let (events, actions) = console.execute_all(
+            "spawn Dragon 1 1; timescale fast; timescale -1; timescale 0; move_speed NaN; \
+             sprint_speed_modifier inf; move_speed; unknown",
+            &mut game_objects,
+        );
+
+        assert!(events.is_empty());
+        assert!(actions.is_empty());
+        assert_eq!(console.cvars, Cvars::default());
+        assert!(!console.take_cheated());
+        assert_eq!(
+            console.lines[console.lines.len() - 13..],
+            [
+                "Not a number: fast".to_string(),
+                "> timescale -1".to_string(),
+                "Usage: timescale <number greater than 0>".to_string(),
+                "> timescale 0".to_string(),
+                "Usage: timescale <number greater than 0>".to_string(),
+                "> move_speed NaN".to_string(),
+                "Usage: move_speed <number 0 or greater>".to_string(),
+                "> sprint_speed_modifier inf".to_string(),
+                "Usage: sprint_speed_modifier <number 0 or greater>".to_string(),
+                "> move_speed".to_string(),
+                format!("move_speed = {}", MOVE_SPEED),
+                "> unknown".to_string(),
+                "Unknown command: unknown (try help)".to_string(),
+            ]
+        );
+    }
+}
@@ -4,7 +4,7 @@ use macroquad::{
     color::Color,
     color_u8,
     input::KeyCode,
-    math::{vec2, Vec2},
+    math::{vec2, vec3, Vec2, Vec3},
 };
 
 pub const FOCUS_KEY: KeyCode = KeyCode::Escape;
@@ -12,10 +12,35 @@ pub const FOCUS_KEY: KeyCode = KeyCode::Escape;
 pub const LEVEL_PATH: &str = "resources/levels/";
 pub const TEXTURE_PATH: &str = "resources/textures/";
 pub const SOUND_PATH: &str = "resources/sounds/";
+pub const DATA_DIR_NAME: &str = "shooter3d";
 pub const COMPLETION_TIMES_PATH: &str = "best.json";
+/// Bumped whenever the layout of the completion times file changes
+pub const COMPLETION_TIMES_VERSION: u32 = 2;
+pub const SETTINGS_PATH: &str = "settings.json";
+pub const SAVES_PATH: &str = "saves/";
+/// Bumped whenever the layout of save files changes
+pub const SAVE_VERSION: u32 = 2;
+pub const SPLITS_PATH: &str = "splits/";
+pub const SCRIPT_EXTENSION: &str = "rhai";
+pub const SCRIPT_MAX_OPERATIONS: u64 = 100_000;
+pub const SCRIPT_MAX_CALL_LEVELS: usize = 32;
+pub const SCRIPT_MAX_STRING_SIZE: usize = 1_000;
+pub const SCRIPT_MAX_COLLECTION_SIZE: usize = 1_000;
 
 pub const START_LEVEL: u32 = 1;
 
+pub const EASY_ENEMY_HEALTH_MODIFIER: f32 = 0.7;
+pub const EASY_ENEMY_DAMAGE_MODIFIER: f32 = 0.6;
+pub const EASY_ENEMY_SPEED_MODIFIER: f32 = 0.85;
+pub const EASY_PLAYER_REGENERATION_MODIFIER: f32 = 1.5;
+pub const EASY_AMMO_MODIFIER: f32 = 1.5;
+
+pub const HARD_ENEMY_HEALTH_MODIFIER: f32 = 1.5;
+pub const HARD_ENEMY_DAMAGE_MODIFIER: f32 = 1.4;
+pub const HARD_ENEMY_SPEED_MODIFIER: f32 = 1.15;
+pub const HARD_PLAYER_REGENERATION_MODIFIER: f32 = 0.5;
+pub const HARD_AMMO_MODIFIER: f32 = 0.75;
+
 pub const HORIZONTAL_WALL_SEGEMENTS: u32 = 300;
 pub const FOV: f32 = PI / 4.0;
 pub const VIEW_DISTANCE: f32 = 1000.0;
@@ -27,16 +52,61 @@ pub const PLAYER_SIZE: f32 = 0.2;
 pub const KEYS_TURN_SPEED: f32 = 1.3;
 pub const MOUSE_TURN_SPEED: f32 = 50.0;
 pub const MOVE_SPEED: f32 = 2.7;
+pub const SPRINT_KEY: KeyCode = KeyCode::LeftShift;
+pub const SPRINT_SPEED_MODIFIER: f32 = 1.6;
+pub const PLAYER_MAX_STAMINA: f32 = 100.0;
+pub const STAMINA_DRAIN: f32 = 30.0;
+pub const STAMINA_REGENERATION: f32 = 18.0;
+pub const STAMINA_EXHAUSTED_THRESHOLD: f32 = 25.0;
+
+pub const VIEW_BOB_TOGGLE_KEY: KeyCode = KeyCode::V;
+pub const LEADERBOARD_KEY: KeyCode = KeyCode::L;
+pub const CONSOLE_KEY: KeyCode = KeyCode::GraveAccent;
+pub const CONSOLE_MAX_LINES: usize = 100;
+pub const CONSOLE_HELP_TEXT: &str = "Commands: god, noclip, give ammo|health, warp <level>, \
+                                      kill_all, spawn <enemy> <x> <y>, debug_map, cvars, \
+                                      <cvar> [value]";
+pub const QUICKSAVE_KEY: KeyCode = KeyCode::F5;
+pub const QUICKLOAD_KEY: KeyCode = KeyCode::F9;
+/// Save to slots 1 to 4, hold `LOAD_SLOT_MODIFIER_KEY` to load instead
+pub const SAVE_SLOT_KEYS: [KeyCode; 4] = [KeyCode::F1, KeyCode::F2, KeyCode::F3, KeyCode::F4];
+pub const LOAD_SLOT_MODIFIER_KEY: KeyCode = KeyCode::LeftShift;
+pub const QUICKSAVE_SLOT: u32 = 0;
+pub const SAVE_MESSAGE_DURATION: f32 = 1.5;
+/// Longer frames, like window drags, only add this much to the level timer
+pub const TIMER_MAX_FRAME_TIME: f32 = 0.25;
+pub const VIEW_BOB_SPEED: u128 = 500;
+pub const VIEW_BOB_AMPLITUDE: f32 = 0.035;
 
 pub const FLOOR_COLOR: Color = color_u8!(55, 55, 75, 255);
 pub const CEILING_COLOR: Color = color_u8!(60, 40, 40, 255);
+pub const DEFAULT_FOG_COLOR: Vec3 = vec3(0.5, 0.5, 0.55);
+/// Walls and sprites showing less than this through the fog aren't drawn
+pub const FOG_MIN_VISIBILITY: f32 = 0.01;
+/// Horizontal stripes per half of the screen used to fade the floor and ceiling into fog
+pub const FOG_BACKGROUND_BANDS: u32 = 48;
 
-pub const MIN_BRIGHTNESS: f32 = 0.2;
+/// Light level of anything neither the player nor a light source reaches
+pub const AMBIENT_BRIGHTNESS: f32 = 0.2;
+/// How far in front of a wall its lighting is sampled, so lights behind it don't shine through
+pub const LIGHT_SURFACE_OFFSET: f32 = 0.01;
+pub const MUZZLE_FLASH_COLOR: Vec3 = vec3(1.0, 0.8, 0.4);
+pub const MUZZLE_FLASH_RADIUS: f32 = 4.0;
+pub const MUZZLE_FLASH_INTENSITY: f32 = 0.8;
+pub const MUZZLE_FLASH_LIFETIME: f32 = 0.08;
+pub const EXPLOSION_LIGHT_COLOR: Vec3 = vec3(1.0, 0.5, 0.1);
+/// Explosion lights reach this many times the blast radius
+pub const EXPLOSION_LIGHT_RADIUS_MOD: f32 = 2.5;
+pub const EXPLOSION_LIGHT_INTENSITY: f32 = 1.5;
+pub const EXPLOSION_LIGHT_LIFETIME: f32 = 0.5;
 
 pub const DEBUG_DRAW_DELAY_MS: u64 = 50;
 pub const DEBUG_INITAL_DRAW_DELAY_MS: u64 = 500;
-pub const ENTER_DEBUG_MODE_KEY: KeyCode = KeyCode::GraveAccent;
+pub const ENTER_DEBUG_MODE_KEY: KeyCode = KeyCode::F10;
 pub const EXIT_DEBUG_MODE_KEY: KeyCode = KeyCode::Delete;
+pub const DEBUG_MAP_KEY: KeyCode = KeyCode::F8;
+pub const AUTOMAP_KEY: KeyCode = KeyCode::Tab;
+pub const MINIMAP_TOGGLE_KEY: KeyCode = KeyCode::M;
 
 pub const KEY_SIZE: f32 = 0.5;
 pub const KEY_ANIMATION_SPEED_MOVEMENT: u128 = 3000;
@@ -49,6 +119,7 @@ pub const GUN_FIRE_ANIMATION_SPEED: u128 = 80;
 pub const GUN_POSITION: Vec2 = vec2(0.6, 0.6);
 pub const GUN_ROTATION: f32 = 0.5;
 pub const GUN_SIZE: f32 = 0.7;
+pub const GUN_SWAY_AMPLITUDE: Vec2 = vec2(0.015, 0.012);
 
 pub const ENEMY_HEIGHT_CHANGE_ANIMATION_SPEED: u128 = 300;
 pub const ENEMY_BASE_HEIGHT_OFFSET: f32 = -0.2;
@@ -56,6 +127,25 @@ pub const ENEMY_HEIGHT_AMPLITUDE: f32 = 0.04;
 pub const ENEMY_SIZE: f32 = 0.8;
 pub const ENEMY_HP: f32 = 100.0;
 pub const ENEMY_MAX_CHASE_DISTANCE: f32 = 40.0;
+pub const ENEMY_COLLISION_SIZE_MODIFIER: f32 = 0.5;
+pub const ENEMY_HIT_FLASH_TIME: f32 = 0.1;
+pub const ENEMY_HIT_FLASH_COLOR: Color = Color::new(1.0, 0.35, 0.35, 1.0);
+pub const ENEMY_STAGGER_TIME: f32 = 0.2;
+pub const ENEMY_KNOCKBACK_DISTANCE: f32 = 0.25;
+pub const ENEMY_DEATH_ANIMATION_FRAMES: usize = 4;
+pub const ENEMY_DEATH_ANIMATION_SPEED: u128 = 90;
+pub const ENEMY_DEATH_DARKEN: f32 = 0.6;
+pub const ENEMY_CORPSE_HEIGHT: f32 = 0.2;
+
+pub const GRATE_COLUMNS: u32 = 8;
+pub const GRATE_ROWS: u32 = 4;
+/// Part of each grate cell taken up by the bars
+pub const GRATE_BAR_WIDTH: f32 = 0.3;
+pub const WINDOW_TEXTURE_SIZE: u16 = 64;
+pub const WINDOW_FRAME_WIDTH: u16 = 4;
+pub const WINDOW_FRAME_COLOR: Color = color_u8!(70, 70, 80, 255);
+pub const WINDOW_GLASS_COLOR: Color = color_u8!(150, 200, 230, 70);
+pub const ENEMY_SEPARATION_ITERATIONS: usize = 2;
 
 pub const MELEE_ENEMY_ANIMATION_SPEED: u128 = 100;
 pub const MELEE_ENEMY_MOVE_SPEED: f32 = 2.4;
@@ -69,6 +159,8 @@ pub const MELEE_SLOW_ENEMY_MOVE_SPEED: f32 = 1.4;
 pub const MELEE_SLOW_ENEMY_ATTACK_RANGE: f32 = 0.1;
 pub const MELEE_SLOW_ENEMY_ATTACK_DELAY: f32 = 2.5;
 pub const MELEE_SLOW_ENEMY_DAMAGE: f32 = 95.0;
+pub const MELEE_SLOW_ENEMY_STAGGER_TIME: f32 = 0.08;
+pub const MELEE_SLOW_ENEMY_KNOCKBACK_DISTANCE: f32 = 0.05;
 
 pub const RANGED_ENEMY_ANIMATION_SPEED: u128 = 100;
 pub const RANGED_ENEMY_SHOOT_RANGE: f32 = 8.0;
@@ -78,6 +170,70 @@ pub const RANGED_ENEMY_DAMAGE: f32 = 30.0;
 pub const RANGED_ENEMY_SHOT_SIZE: f32 = 0.1;
 pub const RANGED_ENEMY_SHOT_SPEED: f32 = 6.0;
 
+pub const KAMIKAZE_ENEMY_HEALTH: f32 = 60.0;
+pub const KAMIKAZE_ENEMY_TINT: Color = Color::new(1.0, 0.6, 0.3, 1.0);
+pub const KAMIKAZE_ENEMY_ANIMATION_SPEED: u128 = 60;
+pub const KAMIKAZE_ENEMY_MOVE_SPEED: f32 = 3.6;
+pub const KAMIKAZE_ENEMY_ATTACK_RANGE: f32 = 0.3;
+pub const KAMIKAZE_ENEMY_DAMAGE: f32 = 70.0;
+pub const KAMIKAZE_EXPLOSION_RADIUS: f32 = 2.5;
+
+pub const EXPLOSION_SIZE_MODIFIER: f32 = 0.6;
+pub const EXPLOSION_OFFSET: f32 = 0.3;
+pub const EXPLOSION_ANIMATION_SPEED: u128 = 60;
+
+pub const BOSS_HEALTH: f32 = 3000.0;
+pub const BOSS_SIZE: f32 = 1.3;
+pub const BOSS_TINT: Color = Color::new(0.75, 0.5, 1.0, 1.0);
+pub const BOSS_ANIMATION_SPEED: u128 = 130;
+pub const BOSS_MOVE_SPEED: f32 = 1.2;
+pub const BOSS_ATTACK_RANGE: f32 = 12.0;
+pub const BOSS_STAGGER_TIME: f32 = 0.0;
+pub const BOSS_KNOCKBACK_DISTANCE: f32 = 0.0;
+pub const BOSS_CHARGE_PHASE_HEALTH: f32 = 0.66;
+pub const BOSS_SUMMON_PHASE_HEALTH: f32 = 0.33;
+pub const BOSS_SPREAD_ATTACK_DELAY: f32 = 1.4;
+pub const BOSS_SPREAD_PROJECTILES: usize = 5;
+pub const BOSS_SPREAD_ANGLE: f32 = 0.6;
+pub const BOSS_SPREAD_DAMAGE: f32 = 20.0;
+pub const BOSS_SHOT_SPEED: f32 = 5.0;
+pub const BOSS_CHARGE_ATTACK_DELAY: f32 = 2.5;
+pub const BOSS_CHARGE_SPEED: f32 = 7.0;
+pub const BOSS_CHARGE_TIME: f32 = 1.2;
+pub const BOSS_CHARGE_DAMAGE: f32 = 60.0;
+pub const BOSS_SUMMON_ATTACK_DELAY: f32 = 5.0;
+pub const BOSS_SUMMON_COUNT: usize = 3;
+pub const BOSS_SUMMON_DISTANCE: f32 = 2.0;
+
+pub const SUMMONER_ENEMY_HEALTH: f32 = 150.0;
+pub const SUMMONER_ENEMY_TINT: Color = Color::new(0.5, 1.0, 0.5, 1.0);
+pub const SUMMONER_ENEMY_ANIMATION_SPEED: u128 = 140;
+pub const SUMMONER_ENEMY_MOVE_SPEED: f32 = 1.5;
+pub const SUMMONER_ENEMY_SUMMON_RANGE: f32 = 7.0;
+pub const SUMMONER_ENEMY_SUMMON_DELAY: f32 = 4.0;
+pub const SUMMONER_ENEMY_SUMMON_COUNT: usize = 2;
+pub const SUMMONER_ENEMY_SUMMON_DISTANCE: f32 = 1.2;
+
+pub const SUMMONED_ENEMY_LIMIT: usize = 12;
+
+pub const MELEE_ENEMY_SCORE: u32 = 100;
+pub const MELEE_SLOW_ENEMY_SCORE: u32 = 150;
+pub const RANGED_ENEMY_SCORE: u32 = 150;
+pub const KAMIKAZE_ENEMY_SCORE: u32 = 120;
+pub const SUMMONER_ENEMY_SCORE: u32 = 250;
+pub const BOSS_SCORE: u32 = 2000;
+
+pub const SURVIVAL_FIRST_WAVE_DELAY: f32 = 3.0;
+pub const SURVIVAL_WAVE_DELAY: f32 = 5.0;
+pub const SURVIVAL_BASE_WAVE_SIZE: u32 = 3;
+pub const SURVIVAL_WAVE_SIZE_INCREASE: u32 = 2;
+pub const SURVIVAL_BOSS_WAVE_INTERVAL: u32 = 5;
+pub const SURVIVAL_SCORE_PER_SECOND: u32 = 10;
+pub const HIGH_SCORE_TABLE_SIZE: usize = 5;
+pub const LEADERBOARD_SIZE: usize = 10;
+pub const DEFAULT_PLAYER_NAME: &str = "Player";
+pub const SPAWNER_DEFAULT_MAX_ALIVE: usize = 3;
+
 pub const RELOAD_SPEED: f32 = 3.0;
 pub const SHOOT_SPEED: f32 = 0.1;
 pub const MAX_BULLETS: usize = 30;
@@ -88,13 +244,32 @@ pub const GUNSHOT_ANIMATION_LENGTH: f32 = 0.3;
 pub const GUNSHOT_ANIMATION_SPEED: u128 = 80;
 pub const CREATE_GUNSHOT_HIT_ANIMATION_OFFSET_TO_CAMERA: f32 = 0.1;
 
-pub const CORPSE_SIZE: f32 = 0.15;
-pub const CORPSE_OFFSET: f32 = 0.45;
+pub const CORPSE_OFFSET: f32 =
+    ENEMY_BASE_HEIGHT_OFFSET - ENEMY_HEIGHT_AMPLITUDE * 0.7 + ENEMY_SIZE * 0.7;
+
+pub const SELECT_DIFFICULTY_TEXT: &str = "Select difficulty";
+pub const SELECT_DIFFICULTY_TEXT_SIZE: f32 = 0.09;
+pub const SELECT_DIFFICULTY_OPTION_TEXT_SIZE: f32 = 0.06;
+pub const SELECT_DIFFICULTY_HINT_TEXT: &str =
+    "Up/Down to choose, Left/Right to change mode, Enter to start";
+pub const SELECT_DIFFICULTY_HINT_TEXT_SIZE: f32 = 0.03;
+
+pub const LEADERBOARD_TEXT_SIZE: f32 = 0.07;
+pub const LEADERBOARD_ENTRY_TEXT_SIZE: f32 = 0.03;
+pub const LEADERBOARD_HINT_TEXT: &str = "Press L to view the leaderboard";
+pub const LEADERBOARD_CLOSE_HINT_TEXT: &str =
+    "Left/Right to change level, Up/Down to change difficulty, L to go back";
+pub const LEADERBOARD_HINT_TEXT_SIZE: f32 = 0.03;
+pub const LEADERBOARD_NEW_RECORD_TEXT_SIZE: f32 = 0.05;
 
 pub const GAME_OVER_TEXT: &str = "Game Over!";
 pub const GAME_OVER_TEXT_SIZE: f32 = 0.15;
+pub const GAME_OVER_SCORE_TEXT_SIZE: f32 = 0.05;
+pub const GAME_OVER_HIGH_SCORE_TEXT_SIZE: f32 = 0.035;
 pub const TRY_AGAIN_TEXT: &str = "Try again (Y/N)?";
 pub const TRY_AGAIN_TEXT_SIZE: f32 = 0.07;
+pub const RESTART_LEVEL_KEY: KeyCode = KeyCode::R;
+pub const RESTART_LEVEL_TEXT: &str = "Y continues from the checkpoint, R restarts the level";
 
 pub const GAME_WON_TEXT: &str = "You win!";
 pub const GAME_WON_TEXT_SIZE: f32 = 0.18;
@@ -109,13 +284,66 @@ pub const LEVEL_WON_TIME_TEXT_SIZE: f32 = 0.08;
 pub const LEVEL_WON_TIME_TEXT_X_OFFSET: f32 = 0.55;
 pub const LEVEL_WON_NEXT_LEVEL_TEXT: &str = "Play next level (Y/N)?";
 pub const LEVEL_WON_NEXT_LEVEL_TEXT_SIZE: f32 = 0.07;
+pub const LEVEL_WON_STATS_TEXT_SIZE: f32 = 0.05;
+
+pub const SECRET_FOUND_TEXT: &str = "You found a secret!";
+pub const SECRET_FOUND_TEXT_DURATION: f32 = 2.0;
+
+pub const MESSAGE_UI_POSITION_Y: f32 = 0.3;
+pub const MESSAGE_UI_TEXT_SIZE: f32 = 0.04;
+pub const MESSAGE_UI_TEXT_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.9);
+pub const MESSAGE_UI_FADE_TIME: f32 = 0.5;
+
+pub const OBJECTIVES_UI_BOX_COLOR: Color = Color::new(0.1, 0.1, 0.1, 0.5);
+pub const OBJECTIVES_UI_TEXT_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.7);
+pub const OBJECTIVES_UI_COMPLETE_TEXT_COLOR: Color = Color::new(0.5, 1.0, 0.5, 0.7);
+pub const OBJECTIVES_UI_SIZE: f32 = 0.05;
+pub const OBJECTIVES_UI_POSITION: Vec2 = vec2(0.05, 0.84);
+
+pub const SPEEDRUN_UI_BOX_COLOR: Color = Color::new(0.1, 0.1, 0.1, 0.5);
+pub const SPEEDRUN_UI_TEXT_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.8);
+pub const SPEEDRUN_UI_AHEAD_COLOR: Color = Color::new(0.4, 1.0, 0.4, 0.8);
+pub const SPEEDRUN_UI_BEHIND_COLOR: Color = Color::new(1.0, 0.4, 0.4, 0.8);
+pub const SPEEDRUN_UI_SIZE: f32 = 0.04;
+pub const SPEEDRUN_UI_POSITION: Vec2 = vec2(0.02, 0.06);
+/// Finished splits listed above the running timer
+pub const SPEEDRUN_UI_SPLITS_SHOWN: usize = 4;
+
+pub const CONSOLE_UI_BOX_COLOR: Color = Color::new(0.0, 0.0, 0.0, 0.75);
+pub const CONSOLE_UI_TEXT_COLOR: Color = Color::new(0.8, 0.8, 0.8, 1.0);
+pub const CONSOLE_UI_INPUT_COLOR: Color = Color::new(1.0, 1.0, 0.6, 1.0);
+pub const CONSOLE_UI_HEIGHT: f32 = 0.4;
+pub const CONSOLE_UI_TEXT_SIZE: f32 = 0.03;
 
-pub const KEYS_UI_BOX_COLOR: Color = Color::new(0.1, 0.1, 0.1, 0.5);
-pub const KEYS_UI_TEXT_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.7);
-pub const KEYS_UI_SIZE: f32 = 0.05;
-pub const KEYS_UI_POSITION: Vec2 = vec2(0.05, 0.84);
-pub const KEYS_UI_BOX_WIDTH_TO_HEIGHT: f32 = 5.0;
-pub const KEYS_UI_FIND_EXIT_TEXT: &str = "Find the exit";
+pub const DEBUG_MAP_BG_COLOR: Color = Color::new(0.05, 0.05, 0.08, 1.0);
+pub const DEBUG_MAP_WALL_COLOR: Color = Color::new(0.9, 0.9, 0.9, 1.0);
+pub const DEBUG_MAP_RAY_COLOR: Color = Color::new(1.0, 1.0, 0.3, 0.15);
+pub const DEBUG_MAP_RAY_HIT_COLOR: Color = Color::new(1.0, 0.5, 0.0, 1.0);
+pub const DEBUG_MAP_PLAYER_COLOR: Color = Color::new(0.3, 0.6, 1.0, 1.0);
+pub const DEBUG_MAP_ENEMY_COLOR: Color = Color::new(1.0, 0.2, 0.2, 1.0);
+pub const DEBUG_MAP_ENEMY_RANGE_COLOR: Color = Color::new(1.0, 0.2, 0.2, 0.4);
+pub const DEBUG_MAP_PROJECTILE_COLOR: Color = Color::new(1.0, 0.3, 1.0, 1.0);
+pub const DEBUG_MAP_KEY_COLOR: Color = Color::new(1.0, 0.85, 0.1, 1.0);
+pub const DEBUG_MAP_EXIT_COLOR: Color = Color::new(0.2, 1.0, 0.4, 0.6);
+/// Fraction of the screen height left empty around the map
+pub const DEBUG_MAP_MARGIN: f32 = 0.05;
+/// Length in world units of the field of view lines drawn from the player
+pub const DEBUG_MAP_FOV_LENGTH: f32 = 5.0;
+
+pub const AUTOMAP_BG_COLOR: Color = Color::new(0.0, 0.0, 0.0, 0.6);
+pub const AUTOMAP_WALL_COLOR: Color = Color::new(0.8, 0.8, 0.8, 0.9);
+pub const AUTOMAP_PLAYER_COLOR: Color = Color::new(0.3, 0.8, 1.0, 1.0);
+pub const AUTOMAP_KEY_COLOR: Color = Color::new(1.0, 0.85, 0.1, 1.0);
+pub const AUTOMAP_COLLECTED_KEY_COLOR: Color = Color::new(0.6, 0.6, 0.6, 0.8);
+pub const AUTOMAP_EXIT_COLOR: Color = Color::new(0.2, 1.0, 0.4, 0.6);
+/// Fraction of the screen height between the map and the screen edge
+pub const AUTOMAP_MARGIN: f32 = 0.03;
+/// Side length of the minimap as a fraction of the screen height
+pub const AUTOMAP_MINIMAP_SIZE: f32 = 0.25;
+/// World units shown from the player to the edge of the minimap
+pub const AUTOMAP_MINIMAP_RANGE: f32 = 10.0;
+pub const AUTOMAP_MARKER_SIZE: f32 = 0.006;
+pub const AUTOMAP_ARROW_SIZE: f32 = 0.015;
 
 pub const BULLETS_UI_BOX_COLOR: Color = Color::new(0.1, 0.1, 0.1, 0.5);
 pub const BULLETS_UI_TEXT_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.7);
@@ -130,7 +358,59 @@ pub const HEALTH_DISPLAY_WIDTH: f32 = 0.14;
 pub const HEALTH_DISPLAY_BACKGROUND_COLOR: Color = Color::new(0.1, 0.1, 0.1, 1.0);
 pub const HEALTH_DISPLAY_COLOR: Color = Color::new(0.2, 0.9, 0.2, 1.0);
 
+pub const STAMINA_DISPLAY_POSITION: Vec2 = vec2(0.21, 0.95);
+pub const STAMINA_DISPLAY_BORDER_SIZE: f32 = 0.005;
+pub const STAMINA_DISPLAY_HEIGHT: f32 = 0.03;
+pub const STAMINA_DISPLAY_WIDTH: f32 = 0.1;
+pub const STAMINA_DISPLAY_BACKGROUND_COLOR: Color = Color::new(0.1, 0.1, 0.1, 1.0);
+pub const STAMINA_DISPLAY_COLOR: Color = Color::new(0.9, 0.8, 0.2, 1.0);
+pub const STAMINA_DISPLAY_EXHAUSTED_COLOR: Color = Color::new(0.6, 0.3, 0.1, 1.0);
+
+pub const BOSS_HEALTH_DISPLAY_POSITION: Vec2 = vec2(0.25, 0.05);
+pub const BOSS_HEALTH_DISPLAY_BORDER_SIZE: f32 = 0.005;
+pub const BOSS_HEALTH_DISPLAY_HEIGHT: f32 = 0.025;
+pub const BOSS_HEALTH_DISPLAY_WIDTH: f32 = 0.5;
+pub const BOSS_HEALTH_DISPLAY_BACKGROUND_COLOR: Color = Color::new(0.1, 0.1, 0.1, 1.0);
+pub const BOSS_HEALTH_DISPLAY_COLOR: Color = Color::new(0.7, 0.1, 0.6, 1.0);
+pub const BOSS_HEALTH_DISPLAY_TEXT: &str = "Boss";
+pub const BOSS_HEALTH_DISPLAY_TEXT_SIZE: f32 = 0.04;
+pub const BOSS_HEALTH_DISPLAY_TEXT_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.8);
+
 pub const PROJECTILE_OFFSET: f32 = 0.0;
 
 pub const EXIT_TEXT_HEIGHT_OFFSET: f32 = -0.2;
 pub const EXIT_TEXT_SIZE: f32 = 1.0;
+
+pub const TELEPORTER_COOLDOWN: f32 = 1.0;
+pub const TELEPORTER_HEIGHT_OFFSET: f32 = 0.45;
+pub const TELEPORTER_DRAW_SIZE_MOD: f32 = 0.8;
+pub const TELEPORTER_TINT: Color = Color::new(0.3, 0.8, 1.0, 0.9);
+pub const TELEPORTER_COOLDOWN_TINT: Color = Color::new(0.2, 0.3, 0.4, 0.6);
+pub const TELEPORT_FLASH_TIME: f32 = 0.4;
+pub const TELEPORT_FLASH_COLOR: Color = Color::new(0.8, 0.95, 1.0, 0.8);
+
+pub const HAZARD_TICK_INTERVAL: f32 = 0.5;
+pub const LAVA_DAMAGE: f32 = 10.0;
+pub const ACID_DAMAGE: f32 = 5.0;
+pub const RADIATION_DAMAGE: f32 = 2.0;
+pub const LAVA_TINT: Color = Color::new(1.0, 0.3, 0.0, 0.25);
+pub const ACID_TINT: Color = Color::new(0.4, 1.0, 0.1, 0.2);
+pub const RADIATION_TINT: Color = Color::new(0.9, 1.0, 0.2, 0.15);
+/// Overlay alpha multiplier while the hazard suit protects the player
+pub const HAZARD_PROTECTED_TINT_MOD: f32 = 0.3;
+pub const HAZARD_SUIT_SIZE: f32 = 0.4;
+pub const HAZARD_SUIT_DURATION: f32 = 20.0;
+pub const HAZARD_SUIT_HEIGHT_OFFSET: f32 = 0.35;
+pub const HAZARD_SUIT_TINT: Color = Color::new(0.7, 1.0, 0.3, 1.0);
+
+pub const CHECKPOINT_SIZE: f32 = 0.5;
+pub const CHECKPOINT_HEIGHT_OFFSET: f32 = 0.45;
+pub const LOCATION_MARKER_SIZE: f32 = 0.4;
+pub const LOCATION_MARKER_HEIGHT_OFFSET: f32 = 0.1;
+pub const LOCATION_MARKER_AMPLITUDE: f32 = 0.05;
+pub const LOCATION_MARKER_SPEED: u128 = 1500;
+pub const LOCATION_MARKER_TINT: Color = Color::new(1.0, 0.85, 0.2, 0.9);
+pub const CHECKPOINT_TINT: Color = Color::new(0.6, 0.6, 0.6, 0.8);
+pub const CHECKPOINT_REACHED_TINT: Color = Color::new(0.3, 1.0, 0.4, 0.9);
+pub const CHECKPOINT_REACHED_TEXT: &str = "Checkpoint reached";
+pub const CHECKPOINT_REACHED_TEXT_DURATION: f32 = 2.0;
@@ -3,16 +3,38 @@ use std::mem::take;
 use macroquad::math::Vec2;
 
 use crate::{
-    constants::RANGED_ENEMY_SHOT_SIZE,
+    console::Cvars,
+    constants::{
+        CHECKPOINT_REACHED_TEXT, CHECKPOINT_REACHED_TEXT_DURATION, EXPLOSION_LIGHT_COLOR,
+        EXPLOSION_LIGHT_INTENSITY, EXPLOSION_LIGHT_LIFETIME, EXPLOSION_LIGHT_RADIUS_MOD,
+        HAZARD_SUIT_DURATION, MUZZLE_FLASH_COLOR, MUZZLE_FLASH_INTENSITY, MUZZLE_FLASH_LIFETIME,
+        MUZZLE_FLASH_RADIUS, RANGED_ENEMY_SHOT_SIZE, SECRET_FOUND_TEXT, SECRET_FOUND_TEXT_DURATION,
+        SUMMONED_ENEMY_LIMIT, TELEPORT_FLASH_TIME,
+    },
     file_loaders::sound_manager::SoundManager,
     input::Operation,
     math::find_perpendicular_vector,
     model::{
-        decoration::Decoration, projectile::Projectile, Entity, GameEvent, GameObjects, Player,
-        PlayerInfo, SoundId, TextureId,
+        decoration::Decoration, enemy::EnemyType, light::Light, projectile::Projectile, Entity,
+        GameEvent, GameObjects, Message, Player, PlayerInfo, SoundId, TextureId, Wall,
     },
     service::{
-        enemy::*, key::check_pickup_key, player::*, projectile::update_projctiles, shoot::*,
+        checkpoint::update_checkpoints,
+        door::close_doors,
+        enemy::*,
+        explosion::{create_explosion_decoration, resolve_explosion},
+        hazard::{check_pickup_hazard_suit, update_hazards},
+        key::check_pickup_key,
+        light::update_lights,
+        objective::are_objectives_complete,
+        player::*,
+        projectile::update_projctiles,
+        shoot::*,
+        spawner::update_spawners,
+        stats::update_secrets,
+        survival::{add_kill_score, update_survival},
+        teleporter::update_teleporters,
+        trigger::{update_message, update_triggers},
     },
 };
 
@@ -24,36 +46,71 @@ fn handle_right(player: Player, angle: f32, delta: f32) -> Player {
     turn_player(player, -angle * delta)
 }
 
-fn handle_forward(game_objects: &GameObjects, player: Player, delta: f32) -> Player {
+fn handle_forward(walls: &[Wall], player: Player, speed: f32, delta: f32) -> Player {
     let direction = player.look;
-    move_player(game_objects, player, direction, delta)
+    move_player(walls, player, direction, speed, delta)
 }
 
-fn handle_back(game_objects: &GameObjects, player: Player, delta: f32) -> Player {
+fn handle_back(walls: &[Wall], player: Player, speed: f32, delta: f32) -> Player {
     let direction = -player.look;
-    move_player(game_objects, player, direction, delta)
+    move_player(walls, player, direction, speed, delta)
 }
 
-fn handle_strafe_left(game_objects: &GameObjects, player: Player, delta: f32) -> Player {
+fn handle_strafe_left(walls: &[Wall], player: Player, speed: f32, delta: f32) -> Player {
     let direction = find_perpendicular_vector(player.look);
-    move_player(game_objects, player, direction, delta)
+    move_player(walls, player, direction, speed, delta)
 }
 
-fn handle_strafe_right(game_objects: &GameObjects, player: Player, delta: f32) -> Player {
+fn handle_strafe_right(walls: &[Wall], player: Player, speed: f32, delta: f32) -> Player {
     let direction = -find_perpendicular_vector(player.look);
-    move_player(game_objects, player, direction, delta)
+    move_player(walls, player, direction, speed, delta)
 }
 
 fn handle_shoot(player_info: &PlayerInfo) -> PlayerInfo {
     start_shooting(player_info.clone())
 }
 
+fn update_movement(
+    old_player: &Player,
+    player: Player,
+    player_info: PlayerInfo,
+    is_sprinting: bool,
+    delta: f32,
+) -> (Player, PlayerInfo) {
+    let movement_speed = if delta > 0.0 {
+        old_player.entity.position.distance(player.entity.position) / delta
+    } else {
+        0.0
+    };
+    let is_moving = movement_speed > 0.0;
+
+    let updated_info = update_stamina(player_info, is_sprinting && is_moving, delta);
+
+    (
+        player,
+        PlayerInfo {
+            movement_speed,
+            ..updated_info
+        },
+    )
+}
+
 pub fn handle_input(
     game_objects: &GameObjects,
     operations: &[Operation],
+    cvars: &Cvars,
     delta: f32,
 ) -> (Player, PlayerInfo) {
-    operations.iter().fold(
+    let is_sprinting =
+        operations.contains(&Operation::Sprint) && can_sprint(&game_objects.player_info);
+    let speed = get_move_speed(is_sprinting, cvars);
+    let walls: &[Wall] = if cvars.noclip {
+        &[]
+    } else {
+        &game_objects.walls
+    };
+
+    let (player, player_info) = operations.iter().fold(
         (
             game_objects.player.clone(),
             game_objects.player_info.clone(),
@@ -61,12 +118,21 @@ pub fn handle_input(
         |(pl, info), op| match op {
             Operation::Left(angle) => (handle_left(pl, *angle, delta), info),
             Operation::Right(angle) => (handle_right(pl, *angle, delta), info),
-            Operation::Forward => (handle_forward(game_objects, pl, delta), info),
-            Operation::Back => (handle_back(game_objects, pl, delta), info),
-            Operation::StrafeLeft => (handle_strafe_left(game_objects, pl, delta), info),
-            Operation::StrafeRight => (handle_strafe_right(game_objects, pl, delta), info),
+            Operation::Forward => (handle_forward(walls, pl, speed, delta), info),
+            Operation::Back => (handle_back(walls, pl, speed, delta), info),
+            Operation::StrafeLeft => (handle_strafe_left(walls, pl, speed, delta), info),
+            Operation::StrafeRight => (handle_strafe_right(walls, pl, speed, delta), info),
             Operation::Shoot => (pl, handle_shoot(&info)),
+            Operation::Sprint => (pl, info),
         },
+    );
+
+    update_movement(
+        &game_objects.player,
+        player,
+        player_info,
+        is_sprinting,
+        delta,
     )
 }
 
@@ -79,13 +145,73 @@ fn handle_pickup_key(sound_manager: &SoundManager, game_objects: &mut GameObject
     println!("Picked up key");
 }
 
-fn handle_enemy_killed(game_objects: &mut GameObjects, position: Vec2) {
+fn handle_pickup_hazard_suit(sound_manager: &SoundManager, game_objects: &mut GameObjects) {
+    game_objects.player_info.hazard_suit_time = HAZARD_SUIT_DURATION;
+    sound_manager.play(SoundId::PickUpKey);
+    println!("Picked up hazard suit");
+}
+
+fn handle_enemy_killed(
+    sound_manager: &SoundManager,
+    game_objects: &mut GameObjects,
+    position: Vec2,
+    enemy_type: EnemyType,
+    name: Option<&String>,
+) -> Vec<GameEvent> {
+    game_objects.killed_targets.extend(name.cloned());
     game_objects.decorations = take(&mut game_objects.decorations)
         .into_iter()
-        .chain(std::iter::once(create_corpse(position)))
+        .chain(std::iter::once(create_death_animation(
+            position, enemy_type,
+        )))
         .collect();
 
+    game_objects.survival =
+        take(&mut game_objects.survival).map(|survival| add_kill_score(survival, enemy_type));
+    game_objects.stats.kills += 1;
+
     println!("Enemy killed at:{}", position);
+
+    match enemy_type.get_death_explosion_radius() {
+        Some(radius) => {
+            let damage = enemy_type.get_attack_damage()
+                * game_objects.difficulty.get_enemy_damage_modifier();
+            handle_explosion(sound_manager, game_objects, position, radius, damage)
+        }
+        None => vec![],
+    }
+}
+
+fn handle_explosion(
+    sound_manager: &SoundManager,
+    game_objects: &mut GameObjects,
+    position: Vec2,
+    radius: f32,
+    damage: f32,
+) -> Vec<GameEvent> {
+    let (enemies, events) = resolve_explosion(
+        position,
+        radius,
+        damage,
+        &game_objects.player,
+        take(&mut game_objects.enemies),
+        &game_objects.walls,
+    );
+    game_objects.enemies = enemies;
+    game_objects
+        .decorations
+        .push(create_explosion_decoration(position, radius));
+    game_objects.lights.push(Light::temporary(
+        position,
+        EXPLOSION_LIGHT_COLOR,
+        radius * EXPLOSION_LIGHT_RADIUS_MOD,
+        EXPLOSION_LIGHT_INTENSITY,
+        EXPLOSION_LIGHT_LIFETIME,
+    ));
+
+    sound_manager.play(SoundId::ShotHit);
+    let chained_events = handle_events(sound_manager, game_objects, &events);
+    events.into_iter().chain(chained_events).collect()
 }
 
 fn handle_location_shot(
@@ -110,6 +236,7 @@ fn handle_player_take_damage(
     damage: f32,
 ) {
     game_objects.player_info.health -= damage;
+    game_objects.stats.damage_taken += damage;
     sound_manager.play(SoundId::PlayerTakeDamage);
     println!("Player took damage({damage})");
 }
@@ -131,15 +258,103 @@ fn handle_create_projectile(
     });
 }
 
+fn handle_spawn_enemy(game_objects: &mut GameObjects, position: Vec2, enemy_type: EnemyType) {
+    if game_objects.enemies.len() >= SUMMONED_ENEMY_LIMIT {
+        return;
+    }
+
+    let enemy = enemy_type.to_enemy(position, game_objects.difficulty);
+    if !get_enemy_intersecting_walls(&enemy.entity, &game_objects.walls).is_empty() {
+        return;
+    }
+
+    game_objects.enemies.push(enemy);
+}
+
+fn handle_open_door(game_objects: &mut GameObjects, name: &str) {
+    let Some(door) = game_objects.doors.iter_mut().find(|door| door.name == name) else {
+        return;
+    };
+
+    door.is_closing = false;
+    if door.is_open {
+        return;
+    }
+
+    door.is_open = true;
+    game_objects
+        .walls
+        .retain(|wall| wall.door.as_deref() != Some(name));
+}
+
+fn handle_close_door(game_objects: &mut GameObjects, name: &str) {
+    if let Some(door) = game_objects
+        .doors
+        .iter_mut()
+        .find(|door| door.name == name && door.is_open)
+    {
+        door.is_closing = true;
+    }
+}
+
+fn handle_show_message(game_objects: &mut GameObjects, text: &str, duration: f32) {
+    game_objects.message = Some(Message {
+        text: text.to_string(),
+        time_left: duration,
+    });
+}
+
+fn handle_shot_fired(game_objects: &mut GameObjects, hit: bool) {
+    game_objects.stats.shots_fired += 1;
+    if hit {
+        game_objects.stats.shots_hit += 1;
+    }
+    game_objects.lights.push(Light::temporary(
+        game_objects.player.entity.position,
+        MUZZLE_FLASH_COLOR,
+        MUZZLE_FLASH_RADIUS,
+        MUZZLE_FLASH_INTENSITY,
+        MUZZLE_FLASH_LIFETIME,
+    ));
+}
+
+fn handle_secret_found(sound_manager: &SoundManager, game_objects: &mut GameObjects) {
+    game_objects.stats.secrets_found += 1;
+    handle_show_message(game_objects, SECRET_FOUND_TEXT, SECRET_FOUND_TEXT_DURATION);
+    sound_manager.play(SoundId::PickUpKey);
+}
+
+fn handle_teleport_player(game_objects: &mut GameObjects, position: Vec2, look: Option<Vec2>) {
+    game_objects.player.entity.position = position;
+    if let Some(look) = look {
+        game_objects.player.look = look.normalize_or(game_objects.player.look);
+    }
+    game_objects.teleport_flash = TELEPORT_FLASH_TIME;
+}
+
+/// Returns the events caused while handling `events`, like kills by an explosion,
+/// which have already been handled
 pub fn handle_events(
     sound_manager: &SoundManager,
     game_objects: &mut GameObjects,
     events: &[GameEvent],
-) {
+) -> Vec<GameEvent> {
+    let mut caused_events = vec![];
     for e in events {
         match e {
             GameEvent::PickUpKey => handle_pickup_key(sound_manager, game_objects),
-            GameEvent::EnemyKilled { position } => handle_enemy_killed(game_objects, *position),
+            GameEvent::PickUpHazardSuit => handle_pickup_hazard_suit(sound_manager, game_objects),
+            GameEvent::EnemyKilled {
+                position,
+                enemy_type,
+                name,
+            } => caused_events.extend(handle_enemy_killed(
+                sound_manager,
+                game_objects,
+                *position,
+                *enemy_type,
+                name.as_ref(),
+            )),
             GameEvent::LocationShot { position } => {
                 handle_location_shot(sound_manager, game_objects, *position)
             }
@@ -151,8 +366,35 @@ pub fn handle_events(
                 direction,
                 damage,
             } => handle_create_projectile(game_objects, *position, *direction, *damage),
+            GameEvent::SpawnEnemy {
+                position,
+                enemy_type,
+            } => handle_spawn_enemy(game_objects, *position, *enemy_type),
+            GameEvent::OpenDoor(name) => handle_open_door(game_objects, name),
+            GameEvent::CloseDoor(name) => handle_close_door(game_objects, name),
+            GameEvent::ShowMessage { text, duration } => {
+                handle_show_message(game_objects, text, *duration)
+            }
+            GameEvent::PlaySound(sound) => sound_manager.play(*sound),
+            GameEvent::ChangeLighting(light_level) => game_objects.light_level = *light_level,
+            GameEvent::ChangeFog(fog) => game_objects.fog = *fog,
+            GameEvent::TeleportPlayer { position, look } => {
+                handle_teleport_player(game_objects, *position, *look)
+            }
+            GameEvent::EndLevel => game_objects.level_ended = true,
+            // Only level scripts react to named triggers
+            GameEvent::TriggerFired(_) => {}
+            GameEvent::ShotFired { hit } => handle_shot_fired(game_objects, *hit),
+            GameEvent::SecretFound => handle_secret_found(sound_manager, game_objects),
+            GameEvent::CheckpointReached => handle_show_message(
+                game_objects,
+                CHECKPOINT_REACHED_TEXT,
+                CHECKPOINT_REACHED_TEXT_DURATION,
+            ),
         }
     }
+
+    caused_events
 }
 
 fn update_decorations(decorations: Vec<Decoration>, delta: f32) -> Vec<Decoration> {
@@ -185,6 +427,28 @@ pub fn next_game_step(game_objects: GameObjects, delta: f32) -> (GameObjects, Ve
         delta,
     );
 
+    let (spawners, spawned_enemies) = update_spawners(
+        game_objects.spawners,
+        &game_objects.player,
+        player_info_shoot.picked_up_keys,
+        &attacked_enemies,
+        game_objects.difficulty,
+        delta,
+    );
+
+    let enemies: Vec<_> = attacked_enemies
+        .into_iter()
+        .chain(spawned_enemies)
+        .collect();
+    let (survival, wave_enemies) = match game_objects.survival {
+        Some(survival) => {
+            let (survival, wave_enemies) =
+                update_survival(survival, &enemies, game_objects.difficulty, delta);
+            (Some(survival), wave_enemies)
+        }
+        None => (None, vec![]),
+    };
+
     let updated_decorations = update_decorations(game_objects.decorations, delta);
 
     let (projectiles, projectile_events) = update_projctiles(
@@ -194,28 +458,82 @@ pub fn next_game_step(game_objects: GameObjects, delta: f32) -> (GameObjects, Ve
         delta,
     );
 
+    let (teleporters, enemies, projectiles, teleport_events) = update_teleporters(
+        game_objects.teleporters,
+        &game_objects.player,
+        enemies.into_iter().chain(wave_enemies).collect(),
+        projectiles,
+        delta,
+    );
+
     let new_player_info = PlayerInfo {
-        health: regenerate_health(player_info_shoot.health, delta),
+        health: regenerate_health(player_info_shoot.health, game_objects.difficulty, delta),
         ..player_info_shoot
     };
 
+    let (new_player_info, hazard_events) = update_hazards(
+        &game_objects.player,
+        new_player_info,
+        &game_objects.hazards,
+        delta,
+    );
+
     let (new_keys, key_events) = check_pickup_key(&game_objects.player, game_objects.keys);
+    let (hazard_suits, hazard_suit_events) =
+        check_pickup_hazard_suit(&game_objects.player, game_objects.hazard_suits);
+    let (secrets, secret_events) = update_secrets(&game_objects.player, game_objects.secrets);
+    let (checkpoints, checkpoint_events) =
+        update_checkpoints(&game_objects.player, game_objects.checkpoints);
+    let (triggers, trigger_events) = update_triggers(&game_objects.player, game_objects.triggers);
+    let (doors, walls) = close_doors(
+        game_objects.doors,
+        game_objects.walls,
+        &game_objects.player,
+        &enemies,
+    );
     let events: Vec<_> = key_events
         .into_iter()
         .chain(kill_enemies_events)
         .chain(attack_events)
         .chain(projectile_events)
+        .chain(trigger_events)
+        .chain(teleport_events)
+        .chain(hazard_events)
+        .chain(hazard_suit_events)
+        .chain(secret_events)
+        .chain(checkpoint_events)
         .collect();
 
     let new_game_objects = GameObjects {
         player: game_objects.player,
         player_info: new_player_info,
-        walls: game_objects.walls,
-        enemies: attacked_enemies,
+        walls,
+        enemies,
         keys: new_keys,
         exit_triggers: game_objects.exit_triggers,
         decorations: updated_decorations,
         projectiles,
+        spawners,
+        difficulty: game_objects.difficulty,
+        survival,
+        objectives: game_objects.objectives,
+        level_time: game_objects.level_time + delta,
+        triggers,
+        doors,
+        teleporters,
+        hazards: game_objects.hazards,
+        hazard_suits,
+        secrets,
+        checkpoints,
+        stats: game_objects.stats,
+        teleport_flash: (game_objects.teleport_flash - delta).max(0.0),
+        message: update_message(game_objects.message, delta),
+        light_level: game_objects.light_level,
+        level_ended: game_objects.level_ended,
+        automap: game_objects.automap,
+        lights: update_lights(game_objects.lights, delta),
+        fog: game_objects.fog,
+        killed_targets: game_objects.killed_targets,
     };
 
     (new_game_objects, events)
@@ -241,5 +559,51 @@ pub fn is_game_over(game_objects: &GameObjects) -> bool {
 }
 
 pub fn is_game_won(game_objects: &GameObjects) -> bool {
-    game_objects.keys.is_empty() && is_player_at_exit(game_objects)
+    game_objects.survival.is_none()
+        && (game_objects.level_ended || are_objectives_complete(game_objects))
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::vec2;
+
+    use crate::model::enemy::Enemy;
+
+    use super::*;
+
+    #[test]
+    fn test_handle_events_returns_explosion_kills() {
+        let mut game_objects = GameObjects {
+            player: Player::at(vec2(20.0, 0.0)),
+            enemies: vec![Enemy {
+                name: Some("guard".to_string()),
+                hp: 1.0,
+                ..EnemyType::Melee.to_enemy(vec2(1.0, 0.0), Default::default())
+            }],
+            ..Default::default()
+        };
+        let kamikaze_killed = GameEvent::EnemyKilled {
+            position: vec2(0.0, 0.0),
+            enemy_type: EnemyType::Kamikaze,
+            name: None,
+        };
+
+        let caused_events = handle_events(
+            &SoundManager::default(),
+            &mut game_objects,
+            &[kamikaze_killed],
+        );
+
+        assert_eq!(
+            caused_events,
+            vec![GameEvent::EnemyKilled {
+                position: vec2(1.0, 0.0),
+                enemy_type: EnemyType::Melee,
+                name: Some("guard".to_string()),
+            }]
+        );
+        assert!(game_objects.enemies.is_empty());
+        assert_eq!(game_objects.killed_targets, vec!["guard".to_string()]);
+        assert_eq!(game_objects.stats.kills, 2);
+    }
 }
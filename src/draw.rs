@@ -1,38 +1,71 @@
 use std::{f32::consts::TAU, iter::once, time::Duration};
 
+use boss_health_display::draw_boss_health_display;
 use bullets_display::draw_bullets_display;
 use exit_text_sprite::create_exit_text;
+use fog_background::draw_fog_background;
 use gun::draw_gun;
+use hazard_overlay::draw_hazard_overlay;
 use health_display::draw_health_display;
-use key_display::draw_key_display;
-use macroquad::math::{vec2, Vec2};
+use location_marker::create_location_markers;
+use macroquad::{
+    color::Color,
+    math::{vec2, Vec2, Vec3},
+};
+use message_display::draw_message_display;
+use objectives_display::draw_objectives_display;
 use sprite_2d::{draw_sprites, Sprite2D};
-use wall::draw_walls;
+use stamina_display::draw_stamina_display;
+use teleport_flash::draw_teleport_flash;
+use wall::{draw_walls, RayHit};
 
 use crate::{
-    constants::{MIN_BRIGHTNESS, VIEW_DISTANCE},
-    file_loaders::texture_manager::TextureManager,
-    model::{Entity, GameObjects, Player, TextureId},
+    constants::{
+        AMBIENT_BRIGHTNESS, MOVE_SPEED, SPRINT_SPEED_MODIFIER, VIEW_BOB_AMPLITUDE, VIEW_BOB_SPEED,
+        VIEW_DISTANCE,
+    },
+    file_loaders::{settings_loader::Settings, texture_manager::TextureManager},
+    model::{fog::Fog, light::Light, Entity, GameObjects, Player, TextureId, Wall},
+    service::light::calculate_light_at,
 };
 
+pub mod automap_display;
+pub mod boss_health_display;
 pub mod bullets_display;
+pub mod console_display;
+pub mod debug_map;
 pub mod exit_text_sprite;
+pub mod fog_background;
 pub mod gun;
+pub mod hazard_overlay;
 pub mod health_display;
-pub mod key_display;
+pub mod location_marker;
+pub mod map_transform;
+pub mod message_display;
+pub mod objectives_display;
+pub mod speedrun_display;
 pub mod sprite_2d;
+pub mod stamina_display;
+pub mod teleport_flash;
 pub mod wall;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Camera {
     pub position: Vec2,
     pub look: Vec2,
+    pub height_offset: f32,
+    /// Multiplies the brightness of everything the camera sees
+    pub light_level: f32,
+    pub fog: Fog,
 }
 impl Camera {
     pub fn for_player(player: &Player) -> Self {
         Self {
             position: player.entity.position,
             look: player.entity.position + player.look.normalize_or_zero() * VIEW_DISTANCE,
+            height_offset: 0.0,
+            light_level: 1.0,
+            fog: Fog::default(),
         }
     }
 }
@@ -43,10 +76,36 @@ pub trait Drawable: Send + Sync {
     fn get_debug_info(&self) -> String;
 }
 
+/// Whether something `distance` away is hidden by fog
+fn is_fogged_out(camera: &Camera, distance: f32) -> bool {
+    camera
+        .fog
+        .get_visible_distance()
+        .is_some_and(|visible_distance| distance >= visible_distance)
+}
+
 fn calculate_brightness(distance: f32) -> f32 {
     (1.0 / distance.max(0.001))
         .sqrt()
-        .clamp(MIN_BRIGHTNESS, 1.0)
+        .clamp(AMBIENT_BRIGHTNESS, 1.0)
+}
+
+/// Light sources of the scene, with the walls that cast their shadows
+#[derive(Clone, Copy)]
+pub struct Lighting<'a> {
+    pub lights: &'a [Light],
+    pub walls: &'a [Wall],
+}
+impl Lighting<'_> {
+    /// Brightness of the player's own light, dimmed by the level's light level,
+    /// plus the coloured lights reaching `point`
+    pub fn get_color(&self, camera: &Camera, point: Vec2) -> Color {
+        let brightness = calculate_brightness(camera.position.distance(point)) * camera.light_level;
+        let light = (Vec3::splat(brightness) + calculate_light_at(point, self.lights, self.walls))
+            .min(Vec3::ONE);
+
+        Color::new(light.x, light.y, light.z, 1.0)
+    }
 }
 
 pub fn select_animation_texture(
@@ -74,11 +133,48 @@ pub fn calculate_vertical_offset(
         - size * VERTICAL_OFFSET_COEF
 }
 
-pub fn draw_game(game_objects: &GameObjects, time_from_start: &Duration) -> Vec<Box<dyn Drawable>> {
-    let camera = Camera::for_player(&game_objects.player);
-    let walls_to_draw = draw_walls(&camera, &game_objects.walls);
+/// Returns a value in 0.0..=1.0 that scales movement effects with how fast the player is moving
+pub fn calculate_movement_intensity(movement_speed: f32) -> f32 {
+    (movement_speed / (MOVE_SPEED * SPRINT_SPEED_MODIFIER)).clamp(0.0, 1.0)
+}
+
+/// Angle of the walk cycle, one full step every `VIEW_BOB_SPEED` milliseconds
+pub fn calculate_walk_cycle(time_from_start: &Duration) -> f32 {
+    (time_from_start.as_millis() % VIEW_BOB_SPEED) as f32 * TAU / VIEW_BOB_SPEED as f32
+}
+
+pub fn calculate_view_bob(time_from_start: &Duration, movement_speed: f32) -> f32 {
+    calculate_walk_cycle(time_from_start).sin().abs()
+        * VIEW_BOB_AMPLITUDE
+        * calculate_movement_intensity(movement_speed)
+}
+
+pub fn draw_game(
+    game_objects: &GameObjects,
+    ray_hits: &[(Vec2, Vec<RayHit>)],
+    time_from_start: &Duration,
+    settings: &Settings,
+) -> Vec<Box<dyn Drawable>> {
+    let movement_speed = if settings.view_bob {
+        game_objects.player_info.movement_speed
+    } else {
+        0.0
+    };
+
+    let camera = Camera {
+        height_offset: calculate_view_bob(time_from_start, movement_speed),
+        light_level: game_objects.light_level,
+        fog: game_objects.fog,
+        ..Camera::for_player(&game_objects.player)
+    };
+    let lighting = Lighting {
+        lights: &game_objects.lights,
+        walls: &game_objects.walls,
+    };
+    let walls_to_draw = draw_walls(&camera, &lighting, ray_hits);
 
     let exit_text_sprite = create_exit_text(game_objects);
+    let location_markers = create_location_markers(game_objects);
     let sprites: Vec<&dyn Sprite2D> = game_objects
         .keys
         .iter()
@@ -86,21 +182,32 @@ pub fn draw_game(game_objects: &GameObjects, time_from_start: &Duration) -> Vec<
         .chain(game_objects.enemies.iter().map(|x| x as &dyn Sprite2D))
         .chain(game_objects.decorations.iter().map(|x| x as &dyn Sprite2D))
         .chain(game_objects.projectiles.iter().map(|x| x as &dyn Sprite2D))
+        .chain(game_objects.teleporters.iter().map(|x| x as &dyn Sprite2D))
+        .chain(game_objects.hazard_suits.iter().map(|x| x as &dyn Sprite2D))
+        .chain(game_objects.checkpoints.iter().map(|x| x as &dyn Sprite2D))
         .chain(exit_text_sprite.iter().map(|x| x.as_ref()))
+        .chain(location_markers.iter().map(|x| x as &dyn Sprite2D))
         .collect();
 
-    let sprites_to_draw = draw_sprites(&camera, time_from_start, &sprites);
+    let sprites_to_draw = draw_sprites(&camera, &lighting, time_from_start, &sprites);
 
     walls_to_draw
         .into_iter()
         .chain(sprites_to_draw)
+        .chain(draw_fog_background(camera.fog))
         .chain(once(draw_gun(
             time_from_start,
             game_objects.player_info.shooting_status,
+            movement_speed,
         )))
-        .chain(once(draw_key_display(game_objects)))
+        .chain(once(draw_objectives_display(game_objects)))
         .chain(once(draw_bullets_display(&game_objects.player_info)))
         .chain(once(draw_health_display(&game_objects.player_info)))
+        .chain(once(draw_stamina_display(&game_objects.player_info)))
+        .chain(draw_hazard_overlay(game_objects))
+        .chain(draw_teleport_flash(game_objects))
+        .chain(draw_boss_health_display(game_objects))
+        .chain(draw_message_display(game_objects))
         .collect()
 }
 
@@ -130,7 +237,57 @@ mod tests {
         assert!((brightness1 - 1.0).abs() < f32::EPSILON);
 
         let brightness2 = calculate_brightness(500.0);
-        assert!((brightness2 - MIN_BRIGHTNESS).abs() < f32::EPSILON);
+        assert!((brightness2 - AMBIENT_BRIGHTNESS).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_lighting_get_color() {
+        let camera = Camera::for_player(&Player {
+            entity: Entity {
+                position: vec2(0.0, 0.0),
+                size: 1.0,
+            },
+            look: vec2(0.0, 1.0),
+        });
+        let lights = [Light::new(
+            vec2(10.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            4.0,
+            4.0,
+        )];
+        let lighting = Lighting {
+            lights: &lights,
+            walls: &[],
+        };
+
+        let unlit = lighting.get_color(&camera, vec2(0.0, 50.0));
+        assert_eq!(
+            unlit,
+            Color::new(
+                AMBIENT_BRIGHTNESS,
+                AMBIENT_BRIGHTNESS,
+                AMBIENT_BRIGHTNESS,
+                1.0
+            )
+        );
+
+        let lit = lighting.get_color(&camera, vec2(10.0, 0.0));
+        assert_eq!(lit.r, 1.0);
+        assert!((lit.g - calculate_brightness(10.0)).abs() < f32::EPSILON);
+        assert_eq!(lit.g, lit.b);
+    }
+
+    #[test]
+    fn test_calculate_view_bob() {
+        let time_from_start = Duration::from_millis(VIEW_BOB_SPEED as u64 / 4);
+
+        assert_eq!(calculate_view_bob(&time_from_start, 0.0), 0.0);
+
+        let walking = calculate_view_bob(&time_from_start, MOVE_SPEED);
+        let sprinting = calculate_view_bob(&time_from_start, MOVE_SPEED * SPRINT_SPEED_MODIFIER);
+        assert!(walking > 0.0);
+        assert!(sprinting > walking);
+        assert!((sprinting - VIEW_BOB_AMPLITUDE).abs() < 0.0001);
     }
 
     #[test]
@@ -0,0 +1,169 @@
+use macroquad::{
+    color::Color,
+    math::{vec2, Rect, Vec2},
+    shapes::{draw_circle, draw_circle_lines, draw_line, draw_rectangle, draw_triangle},
+};
+
+use crate::{
+    constants::{
+        AUTOMAP_ARROW_SIZE, AUTOMAP_BG_COLOR, AUTOMAP_COLLECTED_KEY_COLOR, AUTOMAP_EXIT_COLOR,
+        AUTOMAP_KEY_COLOR, AUTOMAP_MARGIN, AUTOMAP_MARKER_SIZE, AUTOMAP_MINIMAP_RANGE,
+        AUTOMAP_MINIMAP_SIZE, AUTOMAP_PLAYER_COLOR, AUTOMAP_WALL_COLOR,
+    },
+    file_loaders::texture_manager::TextureManager,
+    math::clip_line_to_rect,
+    model::{automap::Automap, GameObjects},
+};
+
+use super::{
+    map_transform::{find_bounds, MapTransform},
+    Drawable,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutomapView {
+    FullScreen,
+    /// Small map in the corner centered on the player
+    Minimap,
+}
+
+struct AutomapDisplay {
+    view: AutomapView,
+    automap: Automap,
+    player_position: Vec2,
+    look: Vec2,
+}
+impl AutomapDisplay {
+    fn get_area(&self, screen_size: (f32, f32)) -> Rect {
+        let margin = AUTOMAP_MARGIN * screen_size.1;
+        match self.view {
+            AutomapView::FullScreen => Rect::new(
+                margin,
+                margin,
+                screen_size.0 - margin * 2.0,
+                screen_size.1 - margin * 2.0,
+            ),
+            AutomapView::Minimap => {
+                let size = AUTOMAP_MINIMAP_SIZE * screen_size.1;
+                Rect::new(screen_size.0 - size - margin, margin, size, size)
+            }
+        }
+    }
+
+    fn get_transform(&self, area: Rect) -> MapTransform {
+        match self.view {
+            AutomapView::FullScreen => {
+                let points = self
+                    .automap
+                    .walls
+                    .iter()
+                    .flat_map(|(start, end)| [*start, *end])
+                    .chain([self.player_position]);
+                let (min, max) = find_bounds(points).unwrap_or_default();
+                MapTransform::fit(min, max, area)
+            }
+            AutomapView::Minimap => MapTransform::centered(
+                self.player_position,
+                area.w / (AUTOMAP_MINIMAP_RANGE * 2.0),
+                area,
+            ),
+        }
+    }
+}
+impl Drawable for AutomapDisplay {
+    fn get_z_index(&self) -> f32 {
+        match self.view {
+            AutomapView::FullScreen => -1.5,
+            AutomapView::Minimap => -1.0,
+        }
+    }
+
+    fn draw(&self, screen_size: (f32, f32), _texture_manager: &TextureManager) {
+        let area = self.get_area(screen_size);
+        let transform = self.get_transform(area);
+        let (min, max) = (area.point(), area.point() + area.size());
+        let marker_size = AUTOMAP_MARKER_SIZE * screen_size.1;
+
+        match self.view {
+            AutomapView::FullScreen => {
+                draw_rectangle(0.0, 0.0, screen_size.0, screen_size.1, AUTOMAP_BG_COLOR)
+            }
+            AutomapView::Minimap => {
+                draw_rectangle(area.x, area.y, area.w, area.h, AUTOMAP_BG_COLOR)
+            }
+        }
+
+        for (start, end) in &self.automap.walls {
+            let start = transform.apply(*start);
+            let end = transform.apply(*end);
+            if let Some((start, end)) = clip_line_to_rect(start, end, min, max) {
+                draw_line(start.x, start.y, end.x, end.y, 2.0, AUTOMAP_WALL_COLOR);
+            }
+        }
+
+        let is_inside = |point: Vec2| area.contains(point);
+        for exit in &self.automap.exits {
+            let center = transform.apply(exit.position);
+            if is_inside(center) {
+                let radius = (exit.size * transform.scale).max(marker_size);
+                draw_circle(center.x, center.y, radius, AUTOMAP_EXIT_COLOR);
+            }
+        }
+        for key in &self.automap.keys {
+            let center = transform.apply(key.position);
+            if !is_inside(center) {
+                continue;
+            }
+            if key.is_collected {
+                draw_circle_lines(
+                    center.x,
+                    center.y,
+                    marker_size,
+                    1.0,
+                    AUTOMAP_COLLECTED_KEY_COLOR,
+                );
+            } else {
+                draw_circle(center.x, center.y, marker_size, AUTOMAP_KEY_COLOR);
+            }
+        }
+
+        draw_player_arrow(
+            transform.apply(self.player_position),
+            self.look,
+            AUTOMAP_ARROW_SIZE * screen_size.1,
+            AUTOMAP_PLAYER_COLOR,
+        );
+    }
+
+    fn get_debug_info(&self) -> String {
+        format!(
+            "AutomapDisplay{{view:{:?} walls:{} keys:{} exits:{}}}",
+            self.view,
+            self.automap.walls.len(),
+            self.automap.keys.len(),
+            self.automap.exits.len()
+        )
+    }
+}
+
+fn draw_player_arrow(center: Vec2, look: Vec2, size: f32, color: Color) {
+    // Screen y points down while world y points up
+    let forward = vec2(look.x, -look.y).normalize_or_zero() * size;
+    let side = forward.perp() * 0.6;
+
+    draw_triangle(
+        center + forward,
+        center - forward * 0.6 + side,
+        center - forward * 0.6 - side,
+        color,
+    );
+}
+
+pub fn draw_automap(game_objects: &GameObjects, view: AutomapView) -> Box<dyn Drawable> {
+    Box::new(AutomapDisplay {
+        view,
+        automap: game_objects.automap.clone(),
+        player_position: game_objects.player.entity.position,
+        look: game_objects.player.look,
+    })
+}
@@ -0,0 +1,72 @@
+use macroquad::{shapes::draw_rectangle, text::draw_text};
+
+use crate::{
+    constants::{
+        BOSS_HEALTH_DISPLAY_BACKGROUND_COLOR, BOSS_HEALTH_DISPLAY_BORDER_SIZE,
+        BOSS_HEALTH_DISPLAY_COLOR, BOSS_HEALTH_DISPLAY_HEIGHT, BOSS_HEALTH_DISPLAY_POSITION,
+        BOSS_HEALTH_DISPLAY_TEXT, BOSS_HEALTH_DISPLAY_TEXT_COLOR, BOSS_HEALTH_DISPLAY_TEXT_SIZE,
+        BOSS_HEALTH_DISPLAY_WIDTH,
+    },
+    file_loaders::texture_manager::TextureManager,
+    model::{enemy::EnemyType, GameObjects},
+};
+
+use super::Drawable;
+
+struct BossHealthDisplay {
+    bar_length: f32,
+}
+impl Drawable for BossHealthDisplay {
+    fn get_z_index(&self) -> f32 {
+        -1.0
+    }
+
+    fn draw(&self, screen_size: (f32, f32), _texture_manager: &TextureManager) {
+        let bg_x =
+            (BOSS_HEALTH_DISPLAY_POSITION.x - BOSS_HEALTH_DISPLAY_BORDER_SIZE) * screen_size.0;
+        let bg_y =
+            (BOSS_HEALTH_DISPLAY_POSITION.y - BOSS_HEALTH_DISPLAY_BORDER_SIZE) * screen_size.1;
+        let bg_width =
+            (BOSS_HEALTH_DISPLAY_BORDER_SIZE * 2.0 + BOSS_HEALTH_DISPLAY_WIDTH) * screen_size.0;
+        let bg_height =
+            (BOSS_HEALTH_DISPLAY_BORDER_SIZE * 2.0 + BOSS_HEALTH_DISPLAY_HEIGHT) * screen_size.1;
+
+        draw_rectangle(
+            bg_x,
+            bg_y,
+            bg_width,
+            bg_height,
+            BOSS_HEALTH_DISPLAY_BACKGROUND_COLOR,
+        );
+        let x = BOSS_HEALTH_DISPLAY_POSITION.x * screen_size.0;
+        let y = BOSS_HEALTH_DISPLAY_POSITION.y * screen_size.1;
+        let width = self.bar_length * screen_size.0;
+        let height = BOSS_HEALTH_DISPLAY_HEIGHT * screen_size.1;
+
+        draw_rectangle(x, y, width, height, BOSS_HEALTH_DISPLAY_COLOR);
+        draw_text(
+            BOSS_HEALTH_DISPLAY_TEXT,
+            x,
+            bg_y,
+            BOSS_HEALTH_DISPLAY_TEXT_SIZE * screen_size.1,
+            BOSS_HEALTH_DISPLAY_TEXT_COLOR,
+        );
+    }
+
+    fn get_debug_info(&self) -> String {
+        format!("BossHealthDisplay{{bar_length:{}}}", self.bar_length)
+    }
+}
+
+/// Shows the health of the first engaged boss, if there is one.
+pub fn draw_boss_health_display(game_objects: &GameObjects) -> Option<Box<dyn Drawable>> {
+    game_objects
+        .enemies
+        .iter()
+        .find(|enemy| enemy.enemy_type == EnemyType::Boss && enemy.is_engaged)
+        .map(|boss| {
+            Box::new(BossHealthDisplay {
+                bar_length: boss.get_health_ratio() * BOSS_HEALTH_DISPLAY_WIDTH,
+            }) as Box<dyn Drawable>
+        })
+}
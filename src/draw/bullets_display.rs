@@ -3,7 +3,7 @@ use macroquad::{shapes::draw_rectangle, text::draw_text};
 use crate::{
     constants::{
         BULLETS_UI_BOX_COLOR, BULLETS_UI_BOX_WIDTH_TO_HEIGHT, BULLETS_UI_POSITION, BULLETS_UI_SIZE,
-        BULLETS_UI_TEXT_COLOR, MAX_BULLETS,
+        BULLETS_UI_TEXT_COLOR,
     },
     file_loaders::texture_manager::TextureManager,
     model::{PlayerInfo, ShootingStatus},
@@ -42,7 +42,10 @@ pub fn draw_bullets_display(player_info: &PlayerInfo) -> Box<dyn Drawable> {
     let text = if matches!(player_info.shooting_status, ShootingStatus::Reloading) {
         "Reloading...".to_string()
     } else {
-        format!("Bullets:{}/{}", player_info.bullets, MAX_BULLETS)
+        format!(
+            "Bullets:{}/{}",
+            player_info.bullets, player_info.max_bullets
+        )
     };
 
     Box::new(BulletsDisplay { text })
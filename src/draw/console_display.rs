@@ -0,0 +1,61 @@
+use macroquad::{shapes::draw_rectangle, text::draw_text};
+
+use crate::{
+    console::Console,
+    constants::{
+        CONSOLE_UI_BOX_COLOR, CONSOLE_UI_HEIGHT, CONSOLE_UI_INPUT_COLOR, CONSOLE_UI_TEXT_COLOR,
+        CONSOLE_UI_TEXT_SIZE,
+    },
+    file_loaders::texture_manager::TextureManager,
+};
+
+use super::Drawable;
+
+struct ConsoleDisplay {
+    lines: Vec<String>,
+    input: String,
+}
+impl Drawable for ConsoleDisplay {
+    fn get_z_index(&self) -> f32 {
+        -2.0
+    }
+
+    fn draw(&self, screen_size: (f32, f32), _texture_manager: &TextureManager) {
+        let h = CONSOLE_UI_HEIGHT * screen_size.1;
+        let line_height = CONSOLE_UI_TEXT_SIZE * screen_size.1;
+        let x = line_height * 0.5;
+        draw_rectangle(0.0, 0.0, screen_size.0, h, CONSOLE_UI_BOX_COLOR);
+
+        // The input sits at the bottom and the newest output directly above it
+        let input_y = h - line_height * 0.5;
+        draw_text(
+            &format!("] {}_", self.input),
+            x,
+            input_y,
+            line_height,
+            CONSOLE_UI_INPUT_COLOR,
+        );
+        for (i, line) in self.lines.iter().rev().enumerate() {
+            let y = input_y - (i + 1) as f32 * line_height;
+            draw_text(line, x, y, line_height, CONSOLE_UI_TEXT_COLOR);
+        }
+    }
+
+    fn get_debug_info(&self) -> String {
+        format!(
+            "ConsoleDisplay{{lines:{},input:{:?}}}",
+            self.lines.len(),
+            self.input
+        )
+    }
+}
+
+pub fn draw_console_display(console: &Console) -> Box<dyn Drawable> {
+    let shown = (CONSOLE_UI_HEIGHT / CONSOLE_UI_TEXT_SIZE) as usize - 1;
+    let first_shown = console.lines.len().saturating_sub(shown);
+
+    Box::new(ConsoleDisplay {
+        lines: console.lines[first_shown..].to_vec(),
+        input: console.input.clone(),
+    })
+}
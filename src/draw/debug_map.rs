@@ -0,0 +1,197 @@
+use macroquad::{
+    color::Color,
+    math::{Rect, Vec2},
+    shapes::{draw_circle, draw_circle_lines, draw_line, draw_rectangle},
+};
+
+use crate::{
+    constants::{
+        DEBUG_MAP_BG_COLOR, DEBUG_MAP_ENEMY_COLOR, DEBUG_MAP_ENEMY_RANGE_COLOR,
+        DEBUG_MAP_EXIT_COLOR, DEBUG_MAP_FOV_LENGTH, DEBUG_MAP_KEY_COLOR, DEBUG_MAP_MARGIN,
+        DEBUG_MAP_PLAYER_COLOR, DEBUG_MAP_PROJECTILE_COLOR, DEBUG_MAP_RAY_COLOR,
+        DEBUG_MAP_RAY_HIT_COLOR, DEBUG_MAP_WALL_COLOR, FOV,
+    },
+    file_loaders::texture_manager::TextureManager,
+    math::rotate_point,
+    model::{Entity, GameObjects},
+};
+
+use super::{
+    map_transform::{find_bounds, MapTransform},
+    wall::RayHit,
+    Drawable,
+};
+
+struct EnemyMarker {
+    entity: Entity,
+    /// Unit vector the enemy moves or charges in
+    facing: Vec2,
+    attack_range: f32,
+}
+
+struct Ray {
+    end: Vec2,
+    hit: Option<Vec2>,
+}
+
+/// Top-down view of the level geometry and everything the simulation tracks
+struct DebugMap {
+    bounds: (Vec2, Vec2),
+    walls: Vec<(Vec2, Vec2)>,
+    rays: Vec<Ray>,
+    enemies: Vec<EnemyMarker>,
+    projectiles: Vec<Entity>,
+    keys: Vec<Entity>,
+    exits: Vec<Entity>,
+    player: Entity,
+    look: Vec2,
+}
+impl DebugMap {
+    fn draw_entity(&self, transform: &MapTransform, entity: &Entity, color: Color) {
+        let center = transform.apply(entity.position);
+        draw_circle(center.x, center.y, entity.size * transform.scale, color);
+    }
+}
+impl Drawable for DebugMap {
+    fn get_z_index(&self) -> f32 {
+        -1.5
+    }
+
+    fn draw(&self, screen_size: (f32, f32), _texture_manager: &TextureManager) {
+        draw_rectangle(0.0, 0.0, screen_size.0, screen_size.1, DEBUG_MAP_BG_COLOR);
+
+        let margin = DEBUG_MAP_MARGIN * screen_size.1;
+        let area = Rect::new(
+            margin,
+            margin,
+            screen_size.0 - margin * 2.0,
+            screen_size.1 - margin * 2.0,
+        );
+        let transform = MapTransform::fit(self.bounds.0, self.bounds.1, area);
+        let line = |start: Vec2, end: Vec2, thickness: f32, color: Color| {
+            let (start, end) = (transform.apply(start), transform.apply(end));
+            draw_line(start.x, start.y, end.x, end.y, thickness, color);
+        };
+
+        for ray in &self.rays {
+            line(
+                self.player.position,
+                ray.hit.unwrap_or(ray.end),
+                1.0,
+                DEBUG_MAP_RAY_COLOR,
+            );
+        }
+        for (start, end) in &self.walls {
+            line(*start, *end, 2.0, DEBUG_MAP_WALL_COLOR);
+        }
+        for hit in self.rays.iter().filter_map(|ray| ray.hit) {
+            let hit = transform.apply(hit);
+            draw_circle(hit.x, hit.y, 2.0, DEBUG_MAP_RAY_HIT_COLOR);
+        }
+
+        for exit in &self.exits {
+            self.draw_entity(&transform, exit, DEBUG_MAP_EXIT_COLOR);
+        }
+        for key in &self.keys {
+            self.draw_entity(&transform, key, DEBUG_MAP_KEY_COLOR);
+        }
+        for projectile in &self.projectiles {
+            self.draw_entity(&transform, projectile, DEBUG_MAP_PROJECTILE_COLOR);
+        }
+        for enemy in &self.enemies {
+            let center = transform.apply(enemy.entity.position);
+            draw_circle_lines(
+                center.x,
+                center.y,
+                enemy.attack_range * transform.scale,
+                1.0,
+                DEBUG_MAP_ENEMY_RANGE_COLOR,
+            );
+            self.draw_entity(&transform, &enemy.entity, DEBUG_MAP_ENEMY_COLOR);
+            line(
+                enemy.entity.position,
+                enemy.entity.position + enemy.facing * enemy.attack_range,
+                2.0,
+                DEBUG_MAP_ENEMY_COLOR,
+            );
+        }
+
+        self.draw_entity(&transform, &self.player, DEBUG_MAP_PLAYER_COLOR);
+        for angle in [-FOV / 2.0, 0.0, FOV / 2.0] {
+            let direction = rotate_point(self.look, Vec2::ZERO, angle);
+            line(
+                self.player.position,
+                self.player.position + direction * DEBUG_MAP_FOV_LENGTH,
+                2.0,
+                DEBUG_MAP_PLAYER_COLOR,
+            );
+        }
+    }
+
+    fn get_debug_info(&self) -> String {
+        format!(
+            "DebugMap{{walls:{} rays:{} enemies:{}}}",
+            self.walls.len(),
+            self.rays.len(),
+            self.enemies.len()
+        )
+    }
+}
+
+pub fn draw_debug_map(
+    game_objects: &GameObjects,
+    ray_hits: &[(Vec2, Vec<RayHit>)],
+) -> Box<dyn Drawable> {
+    let player = &game_objects.player;
+    let rays = ray_hits
+        .iter()
+        .map(|(end, hits)| Ray {
+            end: *end,
+            hit: hits
+                .last()
+                .filter(|hit| !game_objects.walls[hit.wall_index].is_masked)
+                .map(|hit| hit.point),
+        })
+        .collect();
+    let enemies = game_objects
+        .enemies
+        .iter()
+        .map(|enemy| EnemyMarker {
+            entity: enemy.entity,
+            facing: enemy
+                .charge
+                .as_ref()
+                .map(|charge| charge.direction)
+                .unwrap_or(player.entity.position - enemy.entity.position)
+                .normalize_or_zero(),
+            attack_range: enemy.enemy_type.get_attack_range(),
+        })
+        .collect();
+    let bounds = find_bounds(
+        game_objects
+            .walls
+            .iter()
+            .flat_map(|wall| [wall.start, wall.end]),
+    )
+    .unwrap_or((player.entity.position, player.entity.position));
+
+    Box::new(DebugMap {
+        bounds,
+        walls: game_objects
+            .walls
+            .iter()
+            .map(|wall| (wall.start, wall.end))
+            .collect(),
+        rays,
+        enemies,
+        projectiles: game_objects
+            .projectiles
+            .iter()
+            .map(|projectile| projectile.entity)
+            .collect(),
+        keys: game_objects.keys.iter().map(|key| key.entity).collect(),
+        exits: game_objects.exit_triggers.clone(),
+        player: player.entity,
+        look: player.look.normalize_or_zero(),
+    })
+}
@@ -5,7 +5,7 @@ use macroquad::math::Vec2;
 use crate::{
     constants::{EXIT_TEXT_HEIGHT_OFFSET, EXIT_TEXT_SIZE},
     math::check_circles_collide,
-    model::{Entity, GameObjects, TextureId},
+    model::{objective::Objective, Entity, GameObjects, TextureId},
 };
 
 use super::sprite_2d::Sprite2D;
@@ -31,7 +31,16 @@ impl Sprite2D for ExitTextSprite {
     }
 }
 
+/// Reminds the player at the exit to find the keys, on levels that ask for all of them
 pub fn create_exit_text(game_objects: &GameObjects) -> Option<Box<dyn Sprite2D>> {
+    let needs_keys = game_objects
+        .objectives
+        .iter()
+        .any(|objective| objective.includes(&Objective::CollectAllKeys));
+    if !needs_keys {
+        return None;
+    }
+
     let nearest = game_objects
         .exit_triggers
         .iter()
@@ -0,0 +1,53 @@
+use macroquad::shapes::draw_rectangle;
+
+use crate::{
+    constants::{CEILING_COLOR, FLOOR_COLOR, FOG_BACKGROUND_BANDS},
+    file_loaders::texture_manager::TextureManager,
+    model::fog::Fog,
+};
+
+use super::Drawable;
+
+struct FogBackground {
+    fog: Fog,
+}
+impl Drawable for FogBackground {
+    fn get_z_index(&self) -> f32 {
+        f32::INFINITY
+    }
+
+    fn draw(&self, screen_size: (f32, f32), _texture_manager: &TextureManager) {
+        let band_height = 0.5 / FOG_BACKGROUND_BANDS as f32;
+        for band in 0..FOG_BACKGROUND_BANDS {
+            // Floor and ceiling meet walls of height 1.0 / distance
+            let offset = band as f32 * band_height;
+            let distance = 0.5 / (offset + band_height / 2.0);
+
+            draw_rectangle(
+                0.0,
+                (0.5 - offset - band_height) * screen_size.1,
+                screen_size.0,
+                band_height * screen_size.1,
+                self.fog.apply(CEILING_COLOR, distance),
+            );
+            draw_rectangle(
+                0.0,
+                (0.5 + offset) * screen_size.1,
+                screen_size.0,
+                band_height * screen_size.1,
+                self.fog.apply(FLOOR_COLOR, distance),
+            );
+        }
+    }
+
+    fn get_debug_info(&self) -> String {
+        format!("FogBackground{{density:{}}}", self.fog.density)
+    }
+}
+
+/// Floor and ceiling fading into the fog toward the horizon, drawn behind everything
+pub fn draw_fog_background(fog: Fog) -> Option<Box<dyn Drawable>> {
+    fog.get_visible_distance()?;
+
+    Some(Box::new(FogBackground { fog }))
+}
@@ -1,9 +1,14 @@
+use std::f32::consts::PI;
+
 use macroquad::color::WHITE;
 use macroquad::prelude::draw_texture_ex;
 use macroquad::prelude::DrawTextureParams;
 
 use crate::{
-    constants::{GUN_FIRE_ANIMATION_SPEED, GUN_POSITION, GUN_ROTATION, GUN_SIZE},
+    constants::{
+        GUN_FIRE_ANIMATION_SPEED, GUN_POSITION, GUN_ROTATION, GUN_SIZE, GUN_SWAY_AMPLITUDE,
+        VIEW_BOB_SPEED,
+    },
     model::{Animation, ShootingStatus},
 };
 
@@ -11,6 +16,7 @@ use super::*;
 
 struct GunDrawable {
     texture: TextureId,
+    sway: Vec2,
 }
 impl Drawable for GunDrawable {
     fn get_z_index(&self) -> f32 {
@@ -33,19 +39,36 @@ impl Drawable for GunDrawable {
 
         draw_texture_ex(
             texture_2d,
-            (GUN_POSITION.x) * screen_size.0,
-            (GUN_POSITION.y) * screen_size.1,
+            (GUN_POSITION.x + self.sway.x) * screen_size.0,
+            (GUN_POSITION.y + self.sway.y) * screen_size.1,
             WHITE,
             params,
         );
     }
 
     fn get_debug_info(&self) -> String {
-        format!("Gun{{texture:{:?}}}", self.texture)
+        format!("Gun{{texture:{:?} sway:{}}}", self.texture, self.sway)
     }
 }
 
-pub fn draw_gun(time_ellapsed: &Duration, shooting_status: ShootingStatus) -> Box<dyn Drawable> {
+/// Moves the gun in a figure of eight, swinging sideways once for every two steps
+fn calculate_gun_sway(time_ellapsed: &Duration, movement_speed: f32) -> Vec2 {
+    let walk_cycle = calculate_walk_cycle(time_ellapsed);
+    let sway_cycle =
+        (time_ellapsed.as_millis() % (VIEW_BOB_SPEED * 2)) as f32 * PI / VIEW_BOB_SPEED as f32;
+    let intensity = calculate_movement_intensity(movement_speed);
+
+    vec2(
+        sway_cycle.sin() * GUN_SWAY_AMPLITUDE.x,
+        walk_cycle.sin().abs() * GUN_SWAY_AMPLITUDE.y,
+    ) * intensity
+}
+
+pub fn draw_gun(
+    time_ellapsed: &Duration,
+    shooting_status: ShootingStatus,
+    movement_speed: f32,
+) -> Box<dyn Drawable> {
     let shooting_textures = Animation::Gun.get_textures();
 
     let texture = if matches!(shooting_status, ShootingStatus::Shooting) {
@@ -57,5 +80,8 @@ pub fn draw_gun(time_ellapsed: &Duration, shooting_status: ShootingStatus) -> Bo
             .expect("No shooting textures found")
     };
 
-    Box::new(GunDrawable { texture })
+    Box::new(GunDrawable {
+        texture,
+        sway: calculate_gun_sway(time_ellapsed, movement_speed),
+    })
 }
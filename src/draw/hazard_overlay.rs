@@ -0,0 +1,43 @@
+use macroquad::{color::Color, shapes::draw_rectangle};
+
+use crate::{
+    constants::HAZARD_PROTECTED_TINT_MOD, file_loaders::texture_manager::TextureManager,
+    model::GameObjects, service::hazard::find_hazard_at,
+};
+
+use super::Drawable;
+
+struct HazardOverlay {
+    color: Color,
+}
+impl Drawable for HazardOverlay {
+    fn get_z_index(&self) -> f32 {
+        -0.5
+    }
+
+    fn draw(&self, screen_size: (f32, f32), _texture_manager: &TextureManager) {
+        draw_rectangle(0.0, 0.0, screen_size.0, screen_size.1, self.color);
+    }
+
+    fn get_debug_info(&self) -> String {
+        format!("HazardOverlay{{color:{:?}}}", self.color)
+    }
+}
+
+/// Tints the screen with the colour of the hazard the player stands in
+pub fn draw_hazard_overlay(game_objects: &GameObjects) -> Option<Box<dyn Drawable>> {
+    let hazard = find_hazard_at(&game_objects.hazards, &game_objects.player)?;
+    let tint = hazard.hazard_type.get_tint();
+    let alpha_mod = if game_objects.player_info.hazard_suit_time > 0.0 {
+        HAZARD_PROTECTED_TINT_MOD
+    } else {
+        1.0
+    };
+
+    Some(Box::new(HazardOverlay {
+        color: Color {
+            a: tint.a * alpha_mod,
+            ..tint
+        },
+    }))
+}
@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use macroquad::{color::Color, math::Vec2};
+
+use crate::{
+    constants::{
+        LOCATION_MARKER_AMPLITUDE, LOCATION_MARKER_HEIGHT_OFFSET, LOCATION_MARKER_SIZE,
+        LOCATION_MARKER_SPEED, LOCATION_MARKER_TINT,
+    },
+    model::{objective::Objective, GameObjects, TextureId},
+    service::objective::is_objective_complete,
+};
+
+use super::{calculate_vertical_offset, sprite_2d::Sprite2D};
+
+pub struct LocationMarker {
+    position: Vec2,
+}
+impl Sprite2D for LocationMarker {
+    fn get_position(&self) -> Vec2 {
+        self.position
+    }
+
+    fn get_vertical_offset(&self, time_ellapsed: &Duration) -> f32 {
+        calculate_vertical_offset(
+            LOCATION_MARKER_SPEED,
+            self.get_size(),
+            LOCATION_MARKER_HEIGHT_OFFSET,
+            LOCATION_MARKER_AMPLITUDE,
+            time_ellapsed,
+        )
+    }
+
+    fn get_size(&self) -> f32 {
+        LOCATION_MARKER_SIZE
+    }
+
+    fn get_texture(&self, _time_ellapsed: &Duration) -> TextureId {
+        TextureId::Projectile
+    }
+
+    fn get_tint(&self) -> Color {
+        LOCATION_MARKER_TINT
+    }
+}
+
+fn find_locations(objective: &Objective, game_objects: &GameObjects) -> Vec<Vec2> {
+    if is_objective_complete(objective, game_objects) {
+        return vec![];
+    }
+
+    match objective {
+        Objective::ReachLocation { position, .. } => vec![*position],
+        Objective::AllOf(objectives) | Objective::AnyOf(objectives) => objectives
+            .iter()
+            .flat_map(|objective| find_locations(objective, game_objects))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Markers floating over the locations the player still has to reach
+pub fn create_location_markers(game_objects: &GameObjects) -> Vec<LocationMarker> {
+    game_objects
+        .objectives
+        .iter()
+        .flat_map(|objective| find_locations(objective, game_objects))
+        .map(|position| LocationMarker { position })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::vec2;
+
+    use super::*;
+
+    #[test]
+    fn test_create_location_markers() {
+        let game_objects = GameObjects {
+            objectives: vec![
+                Objective::AllOf(vec![
+                    Objective::ReachLocation {
+                        position: vec2(10.0, 0.0),
+                        radius: 1.0,
+                    },
+                    Objective::Survive(5.0),
+                ]),
+                Objective::ReachLocation {
+                    position: vec2(0.0, 0.0),
+                    radius: 1.0,
+                },
+            ],
+            ..Default::default()
+        };
+
+        let markers = create_location_markers(&game_objects);
+
+        assert_eq!(markers.len(), 1);
+        assert_eq!(markers[0].position, vec2(10.0, 0.0));
+    }
+}
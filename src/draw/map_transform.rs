@@ -0,0 +1,60 @@
+use macroquad::math::{vec2, Rect, Vec2};
+
+/// Maps world positions into a screen area for top-down views, world y points up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MapTransform {
+    center: Vec2,
+    screen_center: Vec2,
+    pub scale: f32,
+}
+impl MapTransform {
+    /// Fits the given world bounds into the area, keeping the aspect ratio
+    pub fn fit(min: Vec2, max: Vec2, area: Rect) -> Self {
+        let size = (max - min).max(Vec2::splat(f32::EPSILON));
+        Self {
+            center: (min + max) / 2.0,
+            screen_center: area.center(),
+            scale: (area.w / size.x).min(area.h / size.y),
+        }
+    }
+
+    /// Centers the area on a world position with a fixed scale
+    pub fn centered(center: Vec2, scale: f32, area: Rect) -> Self {
+        Self {
+            center,
+            screen_center: area.center(),
+            scale,
+        }
+    }
+
+    pub fn apply(&self, point: Vec2) -> Vec2 {
+        let offset = (point - self.center) * self.scale;
+        self.screen_center + vec2(offset.x, -offset.y)
+    }
+}
+
+/// Smallest box containing every point, None without points
+pub fn find_bounds(points: impl IntoIterator<Item = Vec2>) -> Option<(Vec2, Vec2)> {
+    points.into_iter().fold(None, |bounds, point| match bounds {
+        Some((min, max)) => Some((point.min(min), point.max(max))),
+        None => Some((point, point)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_level_into_area() {
+        let points = [vec2(0.0, 0.0), vec2(10.0, 0.0), vec2(10.0, 5.0)];
+        let (min, max) = find_bounds(points).unwrap();
+        let transform = MapTransform::fit(min, max, Rect::new(0.0, 0.0, 200.0, 200.0));
+
+        assert_eq!((min, max), (vec2(0.0, 0.0), vec2(10.0, 5.0)));
+        assert_eq!(transform.scale, 20.0);
+        assert_eq!(transform.apply(vec2(0.0, 0.0)), vec2(0.0, 150.0));
+        assert_eq!(transform.apply(vec2(10.0, 5.0)), vec2(200.0, 50.0));
+        assert_eq!(find_bounds([]), None);
+    }
+}
@@ -0,0 +1,51 @@
+use macroquad::{
+    color::Color,
+    text::{draw_text, measure_text},
+};
+
+use crate::{
+    constants::{
+        MESSAGE_UI_FADE_TIME, MESSAGE_UI_POSITION_Y, MESSAGE_UI_TEXT_COLOR, MESSAGE_UI_TEXT_SIZE,
+    },
+    file_loaders::texture_manager::TextureManager,
+    model::GameObjects,
+};
+
+use super::Drawable;
+
+struct MessageDisplay {
+    text: String,
+    alpha: f32,
+}
+impl Drawable for MessageDisplay {
+    fn get_z_index(&self) -> f32 {
+        -1.0
+    }
+
+    fn draw(&self, screen_size: (f32, f32), _texture_manager: &TextureManager) {
+        let font_size = MESSAGE_UI_TEXT_SIZE * screen_size.0;
+        let width = measure_text(&self.text, None, font_size as u16, 1.0).width;
+        let x = (screen_size.0 - width) / 2.0;
+        let y = MESSAGE_UI_POSITION_Y * screen_size.1;
+        let color = Color {
+            a: MESSAGE_UI_TEXT_COLOR.a * self.alpha,
+            ..MESSAGE_UI_TEXT_COLOR
+        };
+
+        draw_text(&self.text, x, y, font_size, color);
+    }
+
+    fn get_debug_info(&self) -> String {
+        format!("MessageDisplay{{text:{} alpha:{}}}", self.text, self.alpha)
+    }
+}
+
+/// Shows the current trigger message, fading out as it expires
+pub fn draw_message_display(game_objects: &GameObjects) -> Option<Box<dyn Drawable>> {
+    let message = game_objects.message.as_ref()?;
+
+    Some(Box::new(MessageDisplay {
+        text: message.text.clone(),
+        alpha: (message.time_left / MESSAGE_UI_FADE_TIME).min(1.0),
+    }))
+}
@@ -0,0 +1,156 @@
+use macroquad::{
+    color::Color,
+    shapes::draw_rectangle,
+    text::{draw_text, measure_text},
+};
+
+use crate::{
+    constants::{
+        OBJECTIVES_UI_BOX_COLOR, OBJECTIVES_UI_COMPLETE_TEXT_COLOR, OBJECTIVES_UI_POSITION,
+        OBJECTIVES_UI_SIZE, OBJECTIVES_UI_TEXT_COLOR,
+    },
+    file_loaders::texture_manager::TextureManager,
+    model::{objective::Objective, GameObjects},
+    service::objective::is_objective_complete,
+};
+
+use super::Drawable;
+
+struct ObjectiveLine {
+    text: String,
+    color: Color,
+}
+
+struct ObjectivesDisplay {
+    lines: Vec<ObjectiveLine>,
+}
+impl Drawable for ObjectivesDisplay {
+    fn get_z_index(&self) -> f32 {
+        -1.0
+    }
+
+    fn draw(&self, screen_size: (f32, f32), _texture_manager: &TextureManager) {
+        let x = OBJECTIVES_UI_POSITION.x * screen_size.0;
+        let h = OBJECTIVES_UI_SIZE * screen_size.1;
+        let font_size = OBJECTIVES_UI_SIZE * screen_size.1 * 0.8;
+
+        let offset_x = 0.2 * OBJECTIVES_UI_SIZE * screen_size.0;
+        let offset_y = 0.2 * OBJECTIVES_UI_SIZE * screen_size.1;
+
+        let text_width = self
+            .lines
+            .iter()
+            .map(|line| measure_text(&line.text, None, font_size as u16, 1.0).width)
+            .fold(0.0, f32::max);
+        let w = text_width + offset_x * 2.0;
+
+        // The last line sits at the position and earlier ones stack above it
+        for (i, line) in self.lines.iter().rev().enumerate() {
+            let y = OBJECTIVES_UI_POSITION.y * screen_size.1 - i as f32 * h;
+            draw_rectangle(
+                x - offset_x,
+                y - h + offset_y,
+                w,
+                h,
+                OBJECTIVES_UI_BOX_COLOR,
+            );
+            draw_text(&line.text, x, y, font_size, line.color);
+        }
+    }
+
+    fn get_debug_info(&self) -> String {
+        let texts: Vec<_> = self.lines.iter().map(|line| line.text.as_str()).collect();
+        format!("ObjectivesDisplay{{lines:{:?}}}", texts)
+    }
+}
+
+fn describe_objective(objective: &Objective, game_objects: &GameObjects) -> String {
+    let picked_up_keys = game_objects.player_info.picked_up_keys;
+
+    match objective {
+        Objective::CollectAllKeys => format!(
+            "Collect keys {}/{}",
+            picked_up_keys,
+            picked_up_keys + game_objects.keys.len()
+        ),
+        Objective::ReachExit => "Find the exit".to_string(),
+        Objective::KillAllEnemies => {
+            format!("Kill all enemies ({} left)", game_objects.enemies.len())
+        }
+        Objective::KillTarget(name) => format!("Kill {}", name),
+        Objective::Survive(seconds) => format!(
+            "Survive {:.0}/{:.0}s",
+            game_objects.level_time.min(*seconds),
+            seconds
+        ),
+        Objective::ReachLocation { .. } => "Reach the marked location".to_string(),
+        Objective::CollectKeys(count) => {
+            format!("Collect keys {}/{}", picked_up_keys.min(*count), count)
+        }
+        Objective::AllOf(objectives) => objectives
+            .iter()
+            .map(|objective| describe_objective(objective, game_objects))
+            .collect::<Vec<_>>()
+            .join(" and "),
+        Objective::AnyOf(objectives) => objectives
+            .iter()
+            .map(|objective| describe_objective(objective, game_objects))
+            .collect::<Vec<_>>()
+            .join(" or "),
+    }
+}
+
+fn create_objective_line(objective: &Objective, game_objects: &GameObjects) -> ObjectiveLine {
+    let text = describe_objective(objective, game_objects);
+    if is_objective_complete(objective, game_objects) {
+        ObjectiveLine {
+            text: format!("[x] {}", text),
+            color: OBJECTIVES_UI_COMPLETE_TEXT_COLOR,
+        }
+    } else {
+        ObjectiveLine {
+            text: format!("[ ] {}", text),
+            color: OBJECTIVES_UI_TEXT_COLOR,
+        }
+    }
+}
+
+pub fn draw_objectives_display(game_objects: &GameObjects) -> Box<dyn Drawable> {
+    let lines = if let Some(survival) = &game_objects.survival {
+        vec![ObjectiveLine {
+            text: format!("Wave:{} Score:{}", survival.wave, survival.get_score()),
+            color: OBJECTIVES_UI_TEXT_COLOR,
+        }]
+    } else {
+        game_objects
+            .objectives
+            .iter()
+            .map(|objective| create_objective_line(objective, game_objects))
+            .collect()
+    };
+
+    Box::new(ObjectivesDisplay { lines })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_objective() {
+        let game_objects = GameObjects {
+            level_time: 12.4,
+            ..Default::default()
+        };
+
+        let objective = Objective::AnyOf(vec![
+            Objective::Survive(30.0),
+            Objective::KillTarget("Warden".to_string()),
+        ]);
+
+        assert_eq!(
+            describe_objective(&objective, &game_objects),
+            "Survive 12/30s or Kill Warden"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use macroquad::{
+    color::Color,
+    shapes::draw_rectangle,
+    text::{draw_text, measure_text},
+};
+
+use crate::{
+    constants::{
+        SPEEDRUN_UI_AHEAD_COLOR, SPEEDRUN_UI_BEHIND_COLOR, SPEEDRUN_UI_BOX_COLOR,
+        SPEEDRUN_UI_POSITION, SPEEDRUN_UI_SIZE, SPEEDRUN_UI_SPLITS_SHOWN, SPEEDRUN_UI_TEXT_COLOR,
+        START_LEVEL,
+    },
+    file_loaders::texture_manager::TextureManager,
+    model::speedrun::Speedrun,
+    renderer::time_to_text,
+};
+
+use super::Drawable;
+
+struct SpeedrunLine {
+    text: String,
+    color: Color,
+}
+
+struct SpeedrunDisplay {
+    lines: Vec<SpeedrunLine>,
+}
+impl Drawable for SpeedrunDisplay {
+    fn get_z_index(&self) -> f32 {
+        -1.0
+    }
+
+    fn draw(&self, screen_size: (f32, f32), _texture_manager: &TextureManager) {
+        let x = SPEEDRUN_UI_POSITION.x * screen_size.0;
+        let h = SPEEDRUN_UI_SIZE * screen_size.1;
+        let font_size = SPEEDRUN_UI_SIZE * screen_size.1 * 0.8;
+        let offset_x = 0.2 * SPEEDRUN_UI_SIZE * screen_size.0;
+        let offset_y = 0.2 * SPEEDRUN_UI_SIZE * screen_size.1;
+
+        let text_width = self
+            .lines
+            .iter()
+            .map(|line| measure_text(&line.text, None, font_size as u16, 1.0).width)
+            .fold(0.0, f32::max);
+        let w = text_width + offset_x * 2.0;
+
+        draw_rectangle(
+            x - offset_x,
+            SPEEDRUN_UI_POSITION.y * screen_size.1 - h + offset_y,
+            w,
+            h * self.lines.len() as f32,
+            SPEEDRUN_UI_BOX_COLOR,
+        );
+        for (i, line) in self.lines.iter().enumerate() {
+            let y = SPEEDRUN_UI_POSITION.y * screen_size.1 + i as f32 * h;
+            draw_text(&line.text, x, y, font_size, line.color);
+        }
+    }
+
+    fn get_debug_info(&self) -> String {
+        let texts: Vec<_> = self.lines.iter().map(|line| line.text.as_str()).collect();
+        format!("SpeedrunDisplay{{lines:{:?}}}", texts)
+    }
+}
+
+fn delta_to_text(delta: f64) -> String {
+    format!("{:+.3}", delta)
+}
+
+fn create_split_line(level: u32, cumulative: Duration, delta: Option<f64>) -> SpeedrunLine {
+    match delta {
+        Some(delta) => SpeedrunLine {
+            text: format!(
+                "L{} {} {}",
+                level,
+                time_to_text(cumulative),
+                delta_to_text(delta)
+            ),
+            color: if delta <= 0.0 {
+                SPEEDRUN_UI_AHEAD_COLOR
+            } else {
+                SPEEDRUN_UI_BEHIND_COLOR
+            },
+        },
+        None => SpeedrunLine {
+            text: format!("L{} {}", level, time_to_text(cumulative)),
+            color: SPEEDRUN_UI_TEXT_COLOR,
+        },
+    }
+}
+
+pub fn draw_speedrun_display(speedrun: &Speedrun, level_time: Duration) -> Box<dyn Drawable> {
+    let cumulative = speedrun.get_cumulative_splits();
+    let first_shown = cumulative.len().saturating_sub(SPEEDRUN_UI_SPLITS_SHOWN);
+    let mut lines: Vec<_> = cumulative
+        .iter()
+        .enumerate()
+        .skip(first_shown)
+        .map(|(i, time)| {
+            create_split_line(START_LEVEL + i as u32, *time, speedrun.get_delta(i, *time))
+        })
+        .collect();
+
+    lines.push(SpeedrunLine {
+        text: format!(
+            "L{} {}",
+            START_LEVEL + cumulative.len() as u32,
+            time_to_text(speedrun.get_total(level_time))
+        ),
+        color: SPEEDRUN_UI_TEXT_COLOR,
+    });
+
+    Box::new(SpeedrunDisplay { lines })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_speedrun_display() {
+        let speedrun = Speedrun {
+            splits: vec![Duration::from_millis(30_500), Duration::from_millis(40_000)],
+            best_splits: vec![Duration::from_secs(31), Duration::from_secs(70)],
+        };
+
+        assert_eq!(
+            draw_speedrun_display(&speedrun, Duration::from_millis(1_250)).get_debug_info(),
+            "SpeedrunDisplay{lines:[\"L1 00:30.500 -0.500\", \"L2 01:10.500 +0.500\", \"L3 01:11.750\"]}"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::constants::FOV;
+use macroquad::color::WHITE;
 use macroquad::prelude::draw_texture_ex;
 use macroquad::prelude::Color;
 use macroquad::prelude::DrawTextureParams;
@@ -11,7 +12,10 @@ struct Sprite2dDrawable {
     texture: TextureId,
     x: f32,
     z_index: f32,
-    brightness: f32,
+    light: Color,
+    /// Fog colour drawn over the sprite, its alpha is how much of the sprite is hidden
+    fog: Color,
+    tint: Color,
     vertical_offset: f32,
     size: f32,
 }
@@ -23,10 +27,10 @@ impl Drawable for Sprite2dDrawable {
     fn draw(&self, screen_size: (f32, f32), texture_manager: &TextureManager) {
         let texture = texture_manager.get_texture(self.texture);
         let color = Color {
-            r: self.brightness,
-            g: self.brightness,
-            b: self.brightness,
-            a: 1.0,
+            r: self.light.r * self.tint.r,
+            g: self.light.g * self.tint.g,
+            b: self.light.b * self.tint.b,
+            a: self.tint.a,
         };
         let texture_size = texture.size();
         let texture_scale = self.size / texture_size.x;
@@ -49,14 +53,27 @@ impl Drawable for Sprite2dDrawable {
             self.x * screen_size.0,
             (0.5 + self.vertical_offset) * screen_size.1,
             color,
-            params,
+            params.clone(),
         );
+        if self.fog.a > 0.0 {
+            let fog = Color {
+                a: self.fog.a * self.tint.a,
+                ..self.fog
+            };
+            draw_texture_ex(
+                texture_manager.get_silhouette(self.texture),
+                self.x * screen_size.0,
+                (0.5 + self.vertical_offset) * screen_size.1,
+                fog,
+                params,
+            );
+        }
     }
 
     fn get_debug_info(&self) -> String {
         format!(
-            "Sprite2D{{x:{:.4} size:{:.4} offset:{:.4} brightness:{:.4}}}",
-            self.x, self.size, self.vertical_offset, self.brightness
+            "Sprite2D{{x:{:.4} size:{:.4} offset:{:.4} light:({:.4},{:.4},{:.4})}}",
+            self.x, self.size, self.vertical_offset, self.light.r, self.light.g, self.light.b
         )
     }
 }
@@ -66,8 +83,12 @@ pub trait Sprite2D: Send + Sync {
     fn get_vertical_offset(&self, time_ellapsed: &Duration) -> f32;
     fn get_size(&self) -> f32;
     fn get_texture(&self, time_ellapsed: &Duration) -> TextureId;
+    fn get_tint(&self) -> Color {
+        WHITE
+    }
 }
 
+#[allow(dead_code)]
 pub struct DebugSprite2D {
     pub entity: Entity,
 }
@@ -91,12 +112,16 @@ impl Sprite2D for DebugSprite2D {
 
 fn sprite_to_drawable(
     time_ellapsed: &Duration,
-    camera_pos: Vec2,
-    camera_look: Vec2,
+    camera: &Camera,
+    lighting: &Lighting,
     sprite: &dyn Sprite2D,
 ) -> Option<Box<dyn Drawable>> {
-    let v = sprite.get_position() - camera_pos;
+    let camera_look = camera.look.normalize_or_zero();
+    let v = sprite.get_position() - camera.position;
     let distance = v.length();
+    if is_fogged_out(camera, distance) {
+        return None;
+    }
     let sprite_size = sprite.get_size() / distance;
     let half_sprite_size = sprite_size / 2.0;
 
@@ -113,22 +138,23 @@ fn sprite_to_drawable(
         texture: sprite.get_texture(time_ellapsed),
         x: screen_x,
         z_index: distance,
-        brightness: calculate_brightness(distance),
-        vertical_offset: sprite.get_vertical_offset(time_ellapsed) * (1.0 / distance),
+        light: lighting.get_color(camera, sprite.get_position()),
+        fog: camera.fog.get_overlay(distance),
+        tint: sprite.get_tint(),
+        vertical_offset: (sprite.get_vertical_offset(time_ellapsed) + camera.height_offset)
+            * (1.0 / distance),
         size: sprite_size,
     }))
 }
 
 pub fn draw_sprites(
     camera: &Camera,
+    lighting: &Lighting,
     time_ellapsed: &Duration,
     sprites: &[&dyn Sprite2D],
 ) -> Vec<Box<dyn Drawable>> {
-    let camera_pos = camera.position;
-    let camera_look = camera.look.normalize_or_zero();
-
     sprites
         .par_iter()
-        .filter_map(|sprite| sprite_to_drawable(time_ellapsed, camera_pos, camera_look, *sprite))
+        .filter_map(|sprite| sprite_to_drawable(time_ellapsed, camera, lighting, *sprite))
         .collect()
 }
@@ -0,0 +1,60 @@
+use macroquad::{color::Color, shapes::draw_rectangle};
+
+use crate::{
+    constants::{
+        PLAYER_MAX_STAMINA, STAMINA_DISPLAY_BACKGROUND_COLOR, STAMINA_DISPLAY_BORDER_SIZE,
+        STAMINA_DISPLAY_COLOR, STAMINA_DISPLAY_EXHAUSTED_COLOR, STAMINA_DISPLAY_HEIGHT,
+        STAMINA_DISPLAY_POSITION, STAMINA_DISPLAY_WIDTH,
+    },
+    file_loaders::texture_manager::TextureManager,
+    model::PlayerInfo,
+};
+
+use super::Drawable;
+
+struct StaminaDisplay {
+    bar_length: f32,
+    color: Color,
+}
+impl Drawable for StaminaDisplay {
+    fn get_z_index(&self) -> f32 {
+        -1.0
+    }
+
+    fn draw(&self, screen_size: (f32, f32), _texture_manager: &TextureManager) {
+        let bg_x = (STAMINA_DISPLAY_POSITION.x - STAMINA_DISPLAY_BORDER_SIZE) * screen_size.0;
+        let bg_y = (STAMINA_DISPLAY_POSITION.y - STAMINA_DISPLAY_BORDER_SIZE) * screen_size.1;
+        let bg_width = (STAMINA_DISPLAY_BORDER_SIZE * 2.0 + STAMINA_DISPLAY_WIDTH) * screen_size.0;
+        let bg_height =
+            (STAMINA_DISPLAY_BORDER_SIZE * 2.0 + STAMINA_DISPLAY_HEIGHT) * screen_size.1;
+
+        draw_rectangle(
+            bg_x,
+            bg_y,
+            bg_width,
+            bg_height,
+            STAMINA_DISPLAY_BACKGROUND_COLOR,
+        );
+        let x = STAMINA_DISPLAY_POSITION.x * screen_size.0;
+        let y = STAMINA_DISPLAY_POSITION.y * screen_size.1;
+        let width = self.bar_length * screen_size.0;
+        let height = STAMINA_DISPLAY_HEIGHT * screen_size.1;
+
+        draw_rectangle(x, y, width, height, self.color);
+    }
+
+    fn get_debug_info(&self) -> String {
+        format!("StaminaDisplay{{bar_length:{}}}", self.bar_length)
+    }
+}
+
+pub fn draw_stamina_display(player_info: &PlayerInfo) -> Box<dyn Drawable> {
+    Box::new(StaminaDisplay {
+        bar_length: (player_info.stamina / PLAYER_MAX_STAMINA) * STAMINA_DISPLAY_WIDTH,
+        color: if player_info.is_exhausted {
+            STAMINA_DISPLAY_EXHAUSTED_COLOR
+        } else {
+            STAMINA_DISPLAY_COLOR
+        },
+    })
+}
@@ -0,0 +1,42 @@
+use macroquad::{color::Color, shapes::draw_rectangle};
+
+use crate::{
+    constants::{TELEPORT_FLASH_COLOR, TELEPORT_FLASH_TIME},
+    file_loaders::texture_manager::TextureManager,
+    model::GameObjects,
+};
+
+use super::Drawable;
+
+struct TeleportFlash {
+    alpha: f32,
+}
+impl Drawable for TeleportFlash {
+    fn get_z_index(&self) -> f32 {
+        -0.5
+    }
+
+    fn draw(&self, screen_size: (f32, f32), _texture_manager: &TextureManager) {
+        let color = Color {
+            a: TELEPORT_FLASH_COLOR.a * self.alpha,
+            ..TELEPORT_FLASH_COLOR
+        };
+
+        draw_rectangle(0.0, 0.0, screen_size.0, screen_size.1, color);
+    }
+
+    fn get_debug_info(&self) -> String {
+        format!("TeleportFlash{{alpha:{}}}", self.alpha)
+    }
+}
+
+/// Screen flash after a teleport, drawn over the world but under the HUD
+pub fn draw_teleport_flash(game_objects: &GameObjects) -> Option<Box<dyn Drawable>> {
+    if game_objects.teleport_flash <= 0.0 {
+        return None;
+    }
+
+    Some(Box::new(TeleportFlash {
+        alpha: (game_objects.teleport_flash / TELEPORT_FLASH_TIME).min(1.0),
+    }))
+}
@@ -3,21 +3,26 @@ use macroquad::{
     math::{vec2, Rect, Vec2},
     texture::{draw_texture_ex, DrawTextureParams},
 };
-use rayon::iter::{ParallelBridge, ParallelIterator};
+use rayon::iter::{
+    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator,
+};
 
 use crate::{
-    constants::{FOV, HORIZONTAL_WALL_SEGEMENTS, WALL_RESOLUTION},
+    constants::{FOV, HORIZONTAL_WALL_SEGEMENTS, LIGHT_SURFACE_OFFSET, WALL_RESOLUTION},
     file_loaders::texture_manager::TextureManager,
     math::{find_intersection, rotate_point},
     model::{TextureId, Wall},
 };
 
-use super::{calculate_brightness, Camera, Drawable};
+use super::{is_fogged_out, Camera, Drawable, Lighting};
 
 struct WallDrawable {
     height: f32,
+    vertical_offset: f32,
     distance: f32,
-    brightness: f32,
+    light: Color,
+    /// Fog colour drawn over the wall, its alpha is how much of the wall is hidden
+    fog: Color,
     x: usize,
     texture: TextureId,
     relative_position: f32,
@@ -28,28 +33,23 @@ impl Drawable for WallDrawable {
     }
 
     fn draw(&self, screen_size: (f32, f32), texture_manager: &TextureManager) {
-        draw_wall(
-            screen_size,
-            texture_manager,
-            self.x,
-            self.height,
-            self.brightness,
-            self.texture,
-            self.relative_position,
-        );
+        draw_wall(screen_size, texture_manager, self);
     }
 
     fn get_debug_info(&self) -> String {
         format!(
-            "Wall{{x:{:.4} brightnes::{:.4} relative_position:{:.4}}}",
-            self.x, self.brightness, self.relative_position
+            "Wall{{x:{:.4} light:({:.4},{:.4},{:.4}) relative_position:{:.4}}}",
+            self.x, self.light.r, self.light.g, self.light.b, self.relative_position
         )
     }
 }
 
-#[derive(Debug)]
-struct RayHit {
-    distance_to_ray: f32,
+#[derive(Debug, Clone, Copy)]
+pub struct RayHit {
+    pub point: Vec2,
+    pub distance_to_ray: f32,
+    /// Index into the walls the ray was cast against
+    pub wall_index: usize,
     texture: TextureId,
     relative_position: f32, // 0.0 start of wall, 1.0 end of wall
 }
@@ -73,49 +73,55 @@ fn calculate_relative_position(hit: Vec2, wall_start: Vec2) -> f32 {
     ((hit_distance * WALL_RESOLUTION) as u64 % WALL_RESOLUTION as u64) as f32 / WALL_RESOLUTION
 }
 
-fn cast_ray(ray_origin: Vec2, ray_direction: Vec2, walls: &[Wall]) -> Option<RayHit> {
-    walls
+/// Every wall the ray hits up to and including the first one it can't see through, nearest first
+fn cast_ray(ray_origin: Vec2, ray_direction: Vec2, walls: &[Wall]) -> Vec<RayHit> {
+    let mut hits: Vec<_> = walls
         .iter()
-        .filter_map(|wall| {
+        .enumerate()
+        .filter_map(|(wall_index, wall)| {
             Some((
+                wall_index,
                 wall,
                 find_intersection(ray_origin, ray_direction, wall.start, wall.end)?,
             ))
         })
-        .map(|(wall, point)| RayHit {
+        .map(|(wall_index, wall, point)| RayHit {
+            point,
             distance_to_ray: point.distance(ray_origin),
+            wall_index,
             texture: wall.texture,
             relative_position: calculate_relative_position(point, wall.start),
         })
-        .min_by(|a, b| a.distance_to_ray.total_cmp(&b.distance_to_ray))
+        .collect();
+    hits.sort_by(|a, b| a.distance_to_ray.total_cmp(&b.distance_to_ray));
+
+    if let Some(opaque) = hits.iter().position(|hit| !walls[hit.wall_index].is_masked) {
+        hits.truncate(opaque + 1);
+    }
+    hits
 }
 
-fn draw_wall(
-    screen_size: (f32, f32),
-    texture_manager: &TextureManager,
-    x: usize,
-    height: f32,
-    brightness: f32,
-    texture: TextureId,
-    relative_position: f32,
-) {
+fn draw_wall(screen_size: (f32, f32), texture_manager: &TextureManager, wall: &WallDrawable) {
     const WALL_WIDTH: f32 = 1.0 / HORIZONTAL_WALL_SEGEMENTS as f32;
-    let center_x = x as f32 / HORIZONTAL_WALL_SEGEMENTS as f32 + WALL_WIDTH / 2.0;
-    let center_y = 0.5;
+    let center_x = wall.x as f32 / HORIZONTAL_WALL_SEGEMENTS as f32 + WALL_WIDTH / 2.0;
+    let center_y = 0.5 + wall.vertical_offset;
 
-    let texture_2d = texture_manager.get_texture(texture);
+    let texture_2d = texture_manager.get_texture(wall.texture);
     let x = center_x - WALL_WIDTH / 2.0;
-    let y = center_y - height / 2.0;
+    let y = center_y - wall.height / 2.0;
 
     let source = Rect::new(
-        texture_2d.width() * relative_position,
+        texture_2d.width() * wall.relative_position,
         0.0,
         texture_2d.width() / HORIZONTAL_WALL_SEGEMENTS as f32,
         texture_2d.height(),
     );
 
     let params = DrawTextureParams {
-        dest_size: Some(vec2(WALL_WIDTH * screen_size.0, height * screen_size.1)),
+        dest_size: Some(vec2(
+            WALL_WIDTH * screen_size.0,
+            wall.height * screen_size.1,
+        )),
         source: Some(source),
         rotation: 0.0,
         flip_x: false,
@@ -126,29 +132,57 @@ fn draw_wall(
         texture_2d,
         x * screen_size.0,
         y * screen_size.1,
-        Color::new(brightness, brightness, brightness, 1.0),
-        params,
+        wall.light,
+        params.clone(),
     );
+    if wall.fog.a > 0.0 {
+        draw_texture_ex(
+            texture_manager.get_silhouette(wall.texture),
+            x * screen_size.0,
+            y * screen_size.1,
+            wall.fog,
+            params,
+        );
+    }
 }
 
-pub fn draw_walls(camera: &Camera, walls: &[Wall]) -> Vec<Box<dyn Drawable>> {
+/// Casts one ray per screen column, left to right, each with its end point and what it hit
+pub fn cast_rays(camera: &Camera, walls: &[Wall]) -> Vec<(Vec2, Vec<RayHit>)> {
     create_rays(camera.look)
-        .iter()
-        .enumerate()
-        .par_bridge()
-        .filter_map(|(x, ray)| -> Option<Box<dyn Drawable>> {
-            let hit = cast_ray(camera.position, *ray, walls)?;
+        .into_par_iter()
+        .map(|ray| (ray, cast_ray(camera.position, ray, walls)))
+        .collect()
+}
 
+pub fn draw_walls(
+    camera: &Camera,
+    lighting: &Lighting,
+    hits: &[(Vec2, Vec<RayHit>)],
+) -> Vec<Box<dyn Drawable>> {
+    hits.par_iter()
+        .enumerate()
+        .flat_map_iter(|(x, (_, hits))| hits.iter().map(move |hit| (x, hit)))
+        .filter_map(|(x, hit)| -> Option<Box<dyn Drawable>> {
             let distance = hit.distance_to_ray;
-            let brightness = calculate_brightness(distance);
+            if is_fogged_out(camera, distance) {
+                return None;
+            }
+
+            // Sampled just in front of the wall, so the wall itself doesn't block its lights
+            let surface_point = hit.point
+                + (camera.position - hit.point).normalize_or_zero() * LIGHT_SURFACE_OFFSET;
+            let light = lighting.get_color(camera, surface_point);
             let texture = hit.texture;
             let relative_position = hit.relative_position;
             let height = 1.0 / distance;
+            let vertical_offset = camera.height_offset / distance;
 
             let drawable = WallDrawable {
                 distance,
                 height,
-                brightness,
+                vertical_offset,
+                light,
+                fog: camera.fog.get_overlay(distance),
                 x,
                 texture,
                 relative_position,
@@ -158,3 +192,37 @@ pub fn draw_walls(camera: &Camera, walls: &[Wall]) -> Vec<Box<dyn Drawable>> {
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wall(x: f32, is_masked: bool) -> Wall {
+        Wall {
+            texture: TextureId::Stone,
+            start: vec2(x, -5.0),
+            end: vec2(x, 5.0),
+            is_masked,
+            shots_pass_through: false,
+            door: None,
+        }
+    }
+
+    #[test]
+    fn test_cast_ray_through_masked_walls() {
+        let walls = [
+            wall(4.0, false),
+            wall(1.0, true),
+            wall(6.0, false),
+            wall(2.0, true),
+        ];
+
+        let hits = cast_ray(vec2(0.0, 0.0), vec2(10.0, 0.0), &walls);
+        let wall_indices: Vec<_> = hits.iter().map(|hit| hit.wall_index).collect();
+        assert_eq!(wall_indices, vec![1, 3, 0]);
+
+        let hits = cast_ray(vec2(0.0, 0.0), vec2(10.0, 0.0), &walls[1..2]);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].point, vec2(1.0, 0.0));
+    }
+}
@@ -1,4 +1,8 @@
 pub mod completion_time_loader;
+pub mod data_dir;
 pub mod level_loader;
+pub mod save_loader;
+pub mod settings_loader;
 pub mod sound_manager;
+pub mod split_loader;
 pub mod texture_manager;
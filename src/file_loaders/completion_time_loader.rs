@@ -1,83 +1,445 @@
 use std::{
     collections::HashMap,
     error::Error,
-    fs::{read, File},
-    io::Write,
+    fs::read,
+    io::ErrorKind,
+    path::{Path, PathBuf},
     time::Duration,
 };
 
 use serde::{Deserialize, Serialize};
 use serde_json::{from_slice, to_string};
 
-use crate::constants::COMPLETION_TIMES_PATH;
+use crate::{
+    constants::{
+        COMPLETION_TIMES_PATH, COMPLETION_TIMES_VERSION, HIGH_SCORE_TABLE_SIZE, LEADERBOARD_SIZE,
+    },
+    file_loaders::data_dir::{backup_corrupt_file, get_data_path, write_atomic},
+    model::{
+        difficulty::Difficulty,
+        leaderboard::{Leaderboard, LeaderboardEntry},
+    },
+};
 
-#[derive(Serialize, Deserialize, Debug)]
-struct BestCompletionTimes {
+/// Best times, high scores and leaderboards, loaded once and saved after every change
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct BestCompletionTimes {
+    /// Files written before versioning are version 0
+    #[serde(default)]
+    version: u32,
+    /// Times recorded before difficulties existed, read as `Difficulty::Normal`
+    #[serde(default, skip_serializing)]
     times: HashMap<u32, Duration>,
+    #[serde(default)]
+    difficulty_times: HashMap<Difficulty, HashMap<u32, Duration>>,
+    /// Times of runs that restarted from a checkpoint, kept apart from full runs
+    #[serde(default)]
+    checkpoint_times: HashMap<Difficulty, HashMap<u32, Duration>>,
+    /// Best survival scores for each level, highest first
+    #[serde(default)]
+    high_scores: HashMap<Difficulty, HashMap<u32, Vec<u32>>>,
+    /// Leaderboards recorded before they were split by difficulty
+    #[serde(default, skip_serializing)]
+    leaderboards: HashMap<u32, Vec<LeaderboardEntry>>,
+    /// Fastest full runs of each level
+    #[serde(default)]
+    difficulty_leaderboards: HashMap<Difficulty, HashMap<u32, Vec<LeaderboardEntry>>>,
+    /// Cumulative split times of the fastest speedrun
+    #[serde(default)]
+    speedrun_splits: HashMap<Difficulty, Vec<Duration>>,
+    #[serde(skip)]
+    path: PathBuf,
 }
 impl BestCompletionTimes {
-    fn read_file() -> Result<Self, Box<dyn Error>> {
-        let data = read(COMPLETION_TIMES_PATH)?;
-        let times: BestCompletionTimes = from_slice(&data)?;
-        Ok(times)
-    }
-
-    fn load() -> Self {
-        Self::read_file().unwrap_or_else(|_| {
-            let times = BestCompletionTimes {
-                times: HashMap::new(),
-            };
-            times.save();
-            times
+    /// Loads from the data directory, importing the file older versions kept in the working directory
+    pub fn load() -> Self {
+        let path = get_data_path(COMPLETION_TIMES_PATH);
+        let legacy_path = Path::new(COMPLETION_TIMES_PATH);
+        let read_path = if !path.exists() && legacy_path.exists() {
+            println!("Importing completion times from {}", legacy_path.display());
+            legacy_path
+        } else {
+            &path
+        };
+
+        Self {
+            path: path.clone(),
+            ..Self::load_from(read_path)
+        }
+    }
+
+    fn load_from(path: &Path) -> Self {
+        let data = match read(path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Self::default().migrate(),
+            Err(err) => {
+                println!("Error reading {}: {}", path.display(), err);
+                return Self::default().migrate();
+            }
+        };
+
+        Self::parse(&data).unwrap_or_else(|err| {
+            println!(
+                "Can't load completion times from {}: {}",
+                path.display(),
+                err
+            );
+            match backup_corrupt_file(path) {
+                Ok(backup_path) => println!("Moved it to {}", backup_path.display()),
+                Err(err) => println!("Error backing up completion times: {}", err),
+            }
+            Self::default().migrate()
         })
     }
 
+    fn parse(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let times: BestCompletionTimes = from_slice(data)?;
+        if times.version > COMPLETION_TIMES_VERSION {
+            return Err(format!("unsupported version {}", times.version).into());
+        }
+
+        Ok(times.migrate())
+    }
+
+    /// Brings a file of any older version up to `COMPLETION_TIMES_VERSION`
+    fn migrate(mut self) -> Self {
+        if self.version < 1 {
+            self = self.migrate_legacy_times();
+        }
+        if self.version < 2 {
+            self = self.migrate_legacy_leaderboards();
+        }
+
+        self.version = COMPLETION_TIMES_VERSION;
+        self
+    }
+
+    fn migrate_legacy_times(mut self) -> Self {
+        let normal_times = self.difficulty_times.entry(Difficulty::Normal).or_default();
+
+        for (level, time) in self.times.drain() {
+            normal_times.entry(level).or_insert(time);
+        }
+
+        self
+    }
+
+    fn migrate_legacy_leaderboards(mut self) -> Self {
+        for (level, entries) in self.leaderboards.drain() {
+            for entry in entries {
+                let entries = self
+                    .difficulty_leaderboards
+                    .entry(entry.difficulty)
+                    .or_default()
+                    .entry(level)
+                    .or_default();
+                insert_leaderboard_entry(entries, entry);
+            }
+        }
+
+        self
+    }
+
+    fn get_times(&self, used_checkpoint: bool) -> &HashMap<Difficulty, HashMap<u32, Duration>> {
+        if used_checkpoint {
+            &self.checkpoint_times
+        } else {
+            &self.difficulty_times
+        }
+    }
+
+    fn get_times_mut(
+        &mut self,
+        used_checkpoint: bool,
+    ) -> &mut HashMap<Difficulty, HashMap<u32, Duration>> {
+        if used_checkpoint {
+            &mut self.checkpoint_times
+        } else {
+            &mut self.difficulty_times
+        }
+    }
+
     fn save(&self) {
-        let json = to_string(self);
-        if json.is_err() {
-            println!("Error serializing completion times: {}", json.unwrap_err());
-            return;
+        let json = match to_string(self) {
+            Ok(json) => json,
+            Err(err) => {
+                println!("Error serializing completion times: {}", err);
+                return;
+            }
+        };
+
+        if let Err(err) = write_atomic(&self.path, json.as_bytes()) {
+            println!("Error saving completion times: {}", err);
         }
+    }
+
+    pub fn get_best_for_level(
+        &self,
+        level: u32,
+        difficulty: Difficulty,
+        used_checkpoint: bool,
+    ) -> Duration {
+        self.get_times(used_checkpoint)
+            .get(&difficulty)
+            .and_then(|times| times.get(&level))
+            .copied()
+            .unwrap_or(Duration::MAX)
+    }
 
-        let file = File::create(COMPLETION_TIMES_PATH);
-        if file.is_err() {
-            println!("Error creating file: {}", file.unwrap_err());
-            return;
+    pub fn save_best_for_level(
+        &mut self,
+        level: u32,
+        difficulty: Difficulty,
+        time: &Duration,
+        used_checkpoint: bool,
+    ) {
+        let best = self
+            .get_times_mut(used_checkpoint)
+            .entry(difficulty)
+            .or_default()
+            .entry(level)
+            .or_insert(*time);
+
+        if *best > *time {
+            *best = *time;
         }
 
-        let write_result = file.unwrap().write_all(json.unwrap().as_bytes());
-        if write_result.is_err() {
-            println!(
-                "Error saving completion times: {}",
-                write_result.unwrap_err()
-            );
+        self.save();
+    }
+
+    pub fn get_high_scores(&self, level: u32, difficulty: Difficulty) -> Vec<u32> {
+        self.high_scores
+            .get(&difficulty)
+            .and_then(|scores| scores.get(&level))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn save_high_score(&mut self, level: u32, difficulty: Difficulty, score: u32) {
+        let scores = self
+            .high_scores
+            .entry(difficulty)
+            .or_default()
+            .entry(level)
+            .or_default();
+
+        insert_high_score(scores, score);
+
+        self.save();
+    }
+
+    pub fn get_leaderboard(&self, level: u32, difficulty: Difficulty) -> Leaderboard {
+        Leaderboard {
+            level,
+            difficulty,
+            entries: self
+                .difficulty_leaderboards
+                .get(&difficulty)
+                .and_then(|leaderboards| leaderboards.get(&level))
+                .cloned()
+                .unwrap_or_default(),
+            new_record: None,
         }
     }
+
+    pub fn save_leaderboard_entry(&mut self, level: u32, entry: LeaderboardEntry) -> Leaderboard {
+        let difficulty = entry.difficulty;
+        let entries = self
+            .difficulty_leaderboards
+            .entry(difficulty)
+            .or_default()
+            .entry(level)
+            .or_default();
+        let new_record = insert_leaderboard_entry(entries, entry);
+        let leaderboard = Leaderboard {
+            level,
+            difficulty,
+            entries: entries.clone(),
+            new_record,
+        };
+
+        self.save();
+        leaderboard
+    }
+
+    pub fn get_best_splits(&self, difficulty: Difficulty) -> Vec<Duration> {
+        self.speedrun_splits
+            .get(&difficulty)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn save_best_splits(&mut self, difficulty: Difficulty, splits: Vec<Duration>) {
+        self.speedrun_splits.insert(difficulty, splits);
+        self.save();
+    }
 }
 
-pub fn load_best_for_level(level: u32) -> Duration {
-    let file = BestCompletionTimes::load();
+fn insert_high_score(scores: &mut Vec<u32>, score: u32) {
+    let position = scores.partition_point(|high_score| *high_score >= score);
+    scores.insert(position, score);
+    scores.truncate(HIGH_SCORE_TABLE_SIZE);
+}
 
-    if file.times.contains_key(&level) {
-        file.times[&level]
-    } else {
-        Duration::MAX
+/// Returns the position of the new entry if it made it onto the leaderboard
+fn insert_leaderboard_entry(
+    entries: &mut Vec<LeaderboardEntry>,
+    entry: LeaderboardEntry,
+) -> Option<usize> {
+    let position = entries.partition_point(|other| other.time <= entry.time);
+    if position >= LEADERBOARD_SIZE {
+        return None;
     }
+
+    entries.insert(position, entry);
+    entries.truncate(LEADERBOARD_SIZE);
+    Some(position)
 }
 
-pub fn save_best_for_level(level: u32, time: &Duration) {
-    let mut file = BestCompletionTimes::load();
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::temp_dir,
+        fs::{remove_dir_all, write},
+        process,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_migrate_legacy_times() {
+        let json = r#"{
+            "times": {"1": {"secs": 50, "nanos": 0}, "2": {"secs": 70, "nanos": 0}},
+            "difficulty_times": {"Normal": {"1": {"secs": 40, "nanos": 0}}}
+        }"#;
+
+        let times = BestCompletionTimes::parse(json.as_bytes()).unwrap();
+        let normal_times = &times.difficulty_times[&Difficulty::Normal];
 
-    match file.times.contains_key(&level) {
-        true => {
-            if file.times[&level] > *time {
-                *file.times.get_mut(&level).unwrap() = *time;
+        assert_eq!(times.version, COMPLETION_TIMES_VERSION);
+        assert!(times.times.is_empty());
+        assert_eq!(normal_times[&1], Duration::from_secs(40));
+        assert_eq!(normal_times[&2], Duration::from_secs(70));
+        assert!(!times.difficulty_times.contains_key(&Difficulty::Hard));
+    }
+
+    #[test]
+    fn test_migrate_legacy_leaderboards() {
+        let entry = |difficulty, secs| LeaderboardEntry {
+            name: "old".to_string(),
+            date: 0,
+            difficulty,
+            time: Duration::from_secs(secs),
+            stats: Default::default(),
+        };
+        let json = serde_json::json!({
+            "version": 1,
+            "leaderboards": {
+                "1": [
+                    entry(Difficulty::Easy, 10),
+                    entry(Difficulty::Hard, 20),
+                    entry(Difficulty::Easy, 30),
+                ]
             }
+        })
+        .to_string();
+
+        let times = BestCompletionTimes::parse(json.as_bytes()).unwrap();
+        let easy = times.get_leaderboard(1, Difficulty::Easy);
+        let hard = times.get_leaderboard(1, Difficulty::Hard);
+
+        assert!(times.leaderboards.is_empty());
+        assert_eq!(easy.entries.len(), 2);
+        assert_eq!(easy.entries[1].time, Duration::from_secs(30));
+        assert_eq!(hard.entries.len(), 1);
+        assert!(times
+            .get_leaderboard(1, Difficulty::Normal)
+            .entries
+            .is_empty());
+    }
+
+    #[test]
+    fn test_reject_newer_version() {
+        let json = format!(r#"{{"version": {}}}"#, COMPLETION_TIMES_VERSION + 1);
+
+        assert!(BestCompletionTimes::parse(json.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_load_and_save() {
+        let dir = temp_dir().join(format!("completion_times_test_{}", process::id()));
+        let path = dir.join(COMPLETION_TIMES_PATH);
+
+        let mut times = BestCompletionTimes {
+            path: path.clone(),
+            ..BestCompletionTimes::load_from(&path)
+        };
+        times.save_best_for_level(1, Difficulty::Hard, &Duration::from_secs(30), false);
+        times.save_best_for_level(1, Difficulty::Hard, &Duration::from_secs(40), false);
+
+        let loaded = BestCompletionTimes::load_from(&path);
+        assert_eq!(loaded.version, COMPLETION_TIMES_VERSION);
+        assert_eq!(
+            loaded.get_best_for_level(1, Difficulty::Hard, false),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            loaded.get_best_for_level(1, Difficulty::Hard, true),
+            Duration::MAX
+        );
+
+        // A corrupt file is moved aside instead of being overwritten
+        write(&path, "{ not json").unwrap();
+        let loaded = BestCompletionTimes::load_from(&path);
+        assert_eq!(
+            loaded.get_best_for_level(1, Difficulty::Hard, false),
+            Duration::MAX
+        );
+        assert!(!path.exists());
+        assert_eq!(dir.read_dir().unwrap().count(), 1);
+
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_insert_high_score() {
+        let mut scores = vec![];
+        for score in [300, 100, 500, 200, 400, 50, 450] {
+            insert_high_score(&mut scores, score);
         }
-        false => {
-            file.times.insert(level, *time);
-        }
+
+        assert_eq!(scores.len(), HIGH_SCORE_TABLE_SIZE);
+        assert_eq!(scores, vec![500, 450, 400, 300, 200]);
     }
 
-    file.save();
+    #[test]
+    fn test_insert_leaderboard_entry() {
+        let entry = |name: &str, secs: u64| LeaderboardEntry {
+            name: name.to_string(),
+            date: 0,
+            difficulty: Difficulty::Normal,
+            time: Duration::from_secs(secs),
+            stats: Default::default(),
+        };
+        let mut entries: Vec<_> = (1..=LEADERBOARD_SIZE as u64)
+            .map(|secs| entry("old", secs * 10))
+            .collect();
+
+        assert_eq!(
+            insert_leaderboard_entry(&mut entries, entry("new", 25)),
+            Some(2)
+        );
+        assert_eq!(entries.len(), LEADERBOARD_SIZE);
+        assert_eq!(entries[2].name, "new");
+
+        // Ties go after the older entry
+        assert_eq!(
+            insert_leaderboard_entry(&mut entries, entry("tie", 10)),
+            Some(1)
+        );
+        assert_eq!(
+            insert_leaderboard_entry(&mut entries, entry("slow", 1000)),
+            None
+        );
+    }
 }
@@ -0,0 +1,99 @@
+use std::{
+    env::var_os,
+    fs::{create_dir_all, rename, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::constants::DATA_DIR_NAME;
+
+fn get_platform_data_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        return var_os("APPDATA").map(PathBuf::from);
+    }
+
+    let home = var_os("HOME").map(PathBuf::from);
+    if cfg!(target_os = "macos") {
+        return home.map(|home| home.join("Library/Application Support"));
+    }
+
+    var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| home.map(|home| home.join(".local/share")))
+}
+
+/// Per-user data directory, the working directory if the platform one can't be found
+pub fn get_data_dir() -> PathBuf {
+    get_platform_data_dir()
+        .map(|dir| dir.join(DATA_DIR_NAME))
+        .unwrap_or_default()
+}
+
+pub fn get_data_path(file_name: &str) -> PathBuf {
+    get_data_dir().join(file_name)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+/// Writes to a temporary file and renames it over `path`, so a crash never leaves half a file
+pub fn write_atomic(path: &Path, data: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        create_dir_all(parent)?;
+    }
+
+    let temp_path = with_suffix(path, ".tmp");
+    let mut file = File::create(&temp_path)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+
+    rename(temp_path, path)
+}
+
+/// Moves an unreadable file aside so it isn't overwritten, returns where it went
+pub fn backup_corrupt_file(path: &Path) -> io::Result<PathBuf> {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs())
+        .unwrap_or_default();
+    let backup_path = with_suffix(path, &format!(".corrupt-{}", secs));
+
+    rename(path, &backup_path)?;
+    Ok(backup_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::temp_dir,
+        fs::{read, remove_dir_all},
+        process,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_write_atomic_and_backup() {
+        let dir = temp_dir().join(format!("data_dir_test_{}", process::id()));
+        let path = dir.join("nested/file.json");
+
+        write_atomic(&path, b"first").unwrap();
+        write_atomic(&path, b"second").unwrap();
+        assert_eq!(read(&path).unwrap(), b"second");
+        assert!(!with_suffix(&path, ".tmp").exists());
+
+        let backup_path = backup_corrupt_file(&path).unwrap();
+        assert!(!path.exists());
+        assert_eq!(read(&backup_path).unwrap(), b"second");
+
+        remove_dir_all(dir).unwrap();
+    }
+}
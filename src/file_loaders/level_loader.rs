@@ -5,8 +5,23 @@ use serde::Deserialize;
 use serde_json::from_slice;
 
 use crate::{
-    constants::{KEY_SIZE, LEVEL_PATH, PLAYER_SIZE},
-    model::{enemy::EnemyType, key_object::KeyObject, Entity, GameObjects, PlayerInfo, TextureId},
+    constants::{
+        CHECKPOINT_SIZE, HAZARD_SUIT_SIZE, KEY_SIZE, LEVEL_PATH, PLAYER_SIZE,
+        SPAWNER_DEFAULT_MAX_ALIVE,
+    },
+    model::{
+        automap::Automap,
+        checkpoint::Checkpoint,
+        difficulty::Difficulty,
+        enemy::EnemyType,
+        game_mode::GameMode,
+        hazard::{HazardSuit, HazardType},
+        key_object::KeyObject,
+        level_stats::LevelStats,
+        spawner::SpawnTrigger,
+        survival::SurvivalState,
+        Entity, GameObjects, PlayerInfo, SoundId, TextureId,
+    },
 };
 
 #[derive(Deserialize)]
@@ -15,6 +30,228 @@ struct Wall {
     end: [f32; 2],
     #[serde(default)]
     texture: TextureId,
+    #[serde(default)]
+    masked: bool,
+    #[serde(default)]
+    shots_pass_through: bool,
+}
+
+impl From<&Wall> for crate::model::Wall {
+    fn from(wall: &Wall) -> Self {
+        crate::model::Wall {
+            texture: wall.texture,
+            start: array_to_vec(wall.start),
+            end: array_to_vec(wall.end),
+            is_masked: wall.masked,
+            shots_pass_through: wall.shots_pass_through,
+            door: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Door {
+    name: String,
+    walls: Vec<Wall>,
+    #[serde(default)]
+    open: bool,
+}
+impl From<&Door> for crate::model::Door {
+    fn from(door: &Door) -> Self {
+        crate::model::Door {
+            name: door.name.clone(),
+            walls: door
+                .walls
+                .iter()
+                .map(|wall| crate::model::Wall {
+                    door: Some(door.name.clone()),
+                    ..wall.into()
+                })
+                .collect(),
+            is_open: door.open,
+            is_closing: false,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+enum TriggerAction {
+    SpawnEnemy {
+        enemy_type: EnemyType,
+        position: [f32; 2],
+    },
+    OpenDoor(String),
+    CloseDoor(String),
+    ShowMessage {
+        text: String,
+        duration: f32,
+    },
+    PlaySound(SoundId),
+    ChangeLighting(f32),
+    ChangeFog(Fog),
+    Teleport([f32; 2]),
+    EndLevel,
+}
+impl From<&TriggerAction> for crate::model::trigger::TriggerAction {
+    fn from(action: &TriggerAction) -> Self {
+        use crate::model::trigger::TriggerAction as ModelTriggerAction;
+
+        match action {
+            TriggerAction::SpawnEnemy {
+                enemy_type,
+                position,
+            } => ModelTriggerAction::SpawnEnemy {
+                enemy_type: *enemy_type,
+                position: array_to_vec(*position),
+            },
+            TriggerAction::OpenDoor(name) => ModelTriggerAction::OpenDoor(name.clone()),
+            TriggerAction::CloseDoor(name) => ModelTriggerAction::CloseDoor(name.clone()),
+            TriggerAction::ShowMessage { text, duration } => ModelTriggerAction::ShowMessage {
+                text: text.clone(),
+                duration: *duration,
+            },
+            TriggerAction::PlaySound(sound) => ModelTriggerAction::PlaySound(*sound),
+            TriggerAction::ChangeLighting(light_level) => {
+                ModelTriggerAction::ChangeLighting(*light_level)
+            }
+            TriggerAction::ChangeFog(fog) => ModelTriggerAction::ChangeFog(fog.into()),
+            TriggerAction::Teleport(position) => {
+                ModelTriggerAction::Teleport(array_to_vec(*position))
+            }
+            TriggerAction::EndLevel => ModelTriggerAction::EndLevel,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Trigger {
+    #[serde(default)]
+    name: Option<String>,
+    position: [f32; 2],
+    size: f32,
+    actions: Vec<TriggerAction>,
+    #[serde(default)]
+    once: bool,
+}
+impl From<&Trigger> for crate::model::trigger::Trigger {
+    fn from(trigger: &Trigger) -> Self {
+        crate::model::trigger::Trigger {
+            name: trigger.name.clone(),
+            entity: Entity {
+                position: array_to_vec(trigger.position),
+                size: trigger.size,
+            },
+            actions: trigger.actions.iter().map(|action| action.into()).collect(),
+            once: trigger.once,
+            has_fired: false,
+            is_player_inside: false,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct TeleporterPad {
+    position: [f32; 2],
+    /// Player look direction when arriving on this pad
+    #[serde(default)]
+    look: Option<[f32; 2]>,
+}
+
+/// Two pads that send the player to each other
+#[derive(Deserialize)]
+struct Teleporter {
+    pads: [TeleporterPad; 2],
+    size: f32,
+    #[serde(default)]
+    teleports_enemies: bool,
+    #[serde(default)]
+    teleports_projectiles: bool,
+}
+impl Teleporter {
+    fn to_teleporters(&self) -> [crate::model::teleporter::Teleporter; 2] {
+        let [first, second] = &self.pads;
+        let pad = |from: &TeleporterPad, to: &TeleporterPad| crate::model::teleporter::Teleporter {
+            entity: Entity {
+                position: array_to_vec(from.position),
+                size: self.size,
+            },
+            destination: array_to_vec(to.position),
+            destination_look: to.look.map(array_to_vec),
+            teleports_enemies: self.teleports_enemies,
+            teleports_projectiles: self.teleports_projectiles,
+            cooldown: 0.0,
+            is_player_inside: false,
+        };
+
+        [pad(first, second), pad(second, first)]
+    }
+}
+
+#[derive(Deserialize)]
+struct Hazard {
+    hazard_type: HazardType,
+    polygon: Vec<[f32; 2]>,
+}
+impl From<&Hazard> for crate::model::hazard::Hazard {
+    fn from(hazard: &Hazard) -> Self {
+        crate::model::hazard::Hazard {
+            hazard_type: hazard.hazard_type,
+            polygon: hazard
+                .polygon
+                .iter()
+                .map(|point| array_to_vec(*point))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Secret {
+    polygon: Vec<[f32; 2]>,
+}
+impl From<&Secret> for crate::model::secret::Secret {
+    fn from(secret: &Secret) -> Self {
+        crate::model::secret::Secret {
+            polygon: secret
+                .polygon
+                .iter()
+                .map(|point| array_to_vec(*point))
+                .collect(),
+            is_found: false,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Light {
+    position: [f32; 2],
+    color: [f32; 3],
+    radius: f32,
+    intensity: f32,
+}
+impl From<&Light> for crate::model::light::Light {
+    fn from(light: &Light) -> Self {
+        crate::model::light::Light::new(
+            array_to_vec(light.position),
+            light.color.into(),
+            light.radius,
+            light.intensity,
+        )
+    }
+}
+
+#[derive(Deserialize)]
+struct Fog {
+    color: [f32; 3],
+    density: f32,
+}
+impl From<&Fog> for crate::model::fog::Fog {
+    fn from(fog: &Fog) -> Self {
+        crate::model::fog::Fog {
+            color: fog.color.into(),
+            density: fog.density,
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -33,10 +270,130 @@ struct ExitTigger {
 struct Enemy {
     position: [f32; 2],
     enemy_type: EnemyType,
+    /// Difficulties the enemy spawns on, spawns on all difficulties if empty
+    #[serde(default)]
+    difficulties: Vec<Difficulty>,
+    /// Lets `KillTarget` objectives refer to the enemy
+    #[serde(default)]
+    name: Option<String>,
+}
+impl Enemy {
+    fn spawns_on(&self, difficulty: Difficulty) -> bool {
+        self.difficulties.is_empty() || self.difficulties.contains(&difficulty)
+    }
+
+    fn to_enemy(&self, difficulty: Difficulty) -> crate::model::enemy::Enemy {
+        crate::model::enemy::Enemy {
+            name: self.name.clone(),
+            ..self
+                .enemy_type
+                .to_enemy(array_to_vec(self.position), difficulty)
+        }
+    }
+}
+
+#[derive(Deserialize)]
+enum Objective {
+    CollectAllKeys,
+    ReachExit,
+    KillAllEnemies,
+    KillTarget(String),
+    Survive(f32),
+    ReachLocation {
+        position: [f32; 2],
+        radius: f32,
+    },
+    #[serde(alias = "CollectItems")]
+    CollectKeys(usize),
+    AllOf(Vec<Objective>),
+    AnyOf(Vec<Objective>),
+}
+impl Objective {
+    /// Names of the enemies this objective asks to kill
+    fn get_targets(&self) -> Vec<&str> {
+        match self {
+            Objective::KillTarget(name) => vec![name],
+            Objective::AllOf(objectives) | Objective::AnyOf(objectives) => objectives
+                .iter()
+                .flat_map(|objective| objective.get_targets())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    fn includes_kill_all_enemies(&self) -> bool {
+        match self {
+            Objective::KillAllEnemies => true,
+            Objective::AllOf(objectives) | Objective::AnyOf(objectives) => objectives
+                .iter()
+                .any(|objective| objective.includes_kill_all_enemies()),
+            _ => false,
+        }
+    }
+}
+impl From<&Objective> for crate::model::objective::Objective {
+    fn from(objective: &Objective) -> Self {
+        use crate::model::objective::Objective as ModelObjective;
+
+        match objective {
+            Objective::CollectAllKeys => ModelObjective::CollectAllKeys,
+            Objective::ReachExit => ModelObjective::ReachExit,
+            Objective::KillAllEnemies => ModelObjective::KillAllEnemies,
+            Objective::KillTarget(name) => ModelObjective::KillTarget(name.clone()),
+            Objective::Survive(seconds) => ModelObjective::Survive(*seconds),
+            Objective::ReachLocation { position, radius } => ModelObjective::ReachLocation {
+                position: array_to_vec(*position),
+                radius: *radius,
+            },
+            Objective::CollectKeys(count) => ModelObjective::CollectKeys(*count),
+            Objective::AllOf(objectives) => {
+                ModelObjective::AllOf(objectives.iter().map(|o| o.into()).collect())
+            }
+            Objective::AnyOf(objectives) => {
+                ModelObjective::AnyOf(objectives.iter().map(|o| o.into()).collect())
+            }
+        }
+    }
+}
+
+fn default_max_alive() -> usize {
+    SPAWNER_DEFAULT_MAX_ALIVE
+}
+
+#[derive(Deserialize)]
+struct Spawner {
+    position: [f32; 2],
+    enemy_type: EnemyType,
+    #[serde(default)]
+    trigger: SpawnTrigger,
+    /// Seconds between spawns
+    interval: f32,
+    #[serde(default = "default_max_alive")]
+    max_alive: usize,
+    /// Total number of enemies to spawn, unlimited if missing
+    #[serde(default)]
+    count: Option<u32>,
+    /// Difficulties the spawner is active on, active on all difficulties if empty
+    #[serde(default)]
+    difficulties: Vec<Difficulty>,
 }
-impl Into<crate::model::enemy::Enemy> for Enemy {
-    fn into(self) -> crate::model::enemy::Enemy {
-        self.enemy_type.to_enemy(array_to_vec(self.position))
+impl Spawner {
+    fn spawns_on(&self, difficulty: Difficulty) -> bool {
+        self.difficulties.is_empty() || self.difficulties.contains(&difficulty)
+    }
+
+    fn to_spawner(&self) -> crate::model::spawner::Spawner {
+        crate::model::spawner::Spawner {
+            position: array_to_vec(self.position),
+            enemy_type: self.enemy_type,
+            trigger: self.trigger,
+            is_triggered: false,
+            interval: self.interval,
+            time_until_spawn: 0.0,
+            max_alive: self.max_alive,
+            remaining: self.count,
+            spawned_ids: vec![],
+        }
     }
 }
 
@@ -47,10 +404,84 @@ struct Level {
     enemies: Vec<Enemy>,
     keys: Vec<[f32; 2]>,
     exit_triggers: Vec<ExitTigger>,
+    #[serde(default)]
+    spawners: Vec<Spawner>,
+    /// Where survival waves spawn, enemy positions are used if empty
+    #[serde(default)]
+    survival_spawn_points: Vec<[f32; 2]>,
+    /// Collect all keys and reach the exit if empty
+    #[serde(default)]
+    objectives: Vec<Objective>,
+    #[serde(default)]
+    triggers: Vec<Trigger>,
+    #[serde(default)]
+    doors: Vec<Door>,
+    #[serde(default)]
+    teleporters: Vec<Teleporter>,
+    #[serde(default)]
+    hazards: Vec<Hazard>,
+    #[serde(default)]
+    hazard_suits: Vec<[f32; 2]>,
+    #[serde(default)]
+    secrets: Vec<Secret>,
+    #[serde(default)]
+    checkpoints: Vec<[f32; 2]>,
+    #[serde(default)]
+    lights: Vec<Light>,
+    /// Clear air if missing
+    fog: Option<Fog>,
 }
 
-impl Into<GameObjects> for Level {
-    fn into(self) -> GameObjects {
+impl Level {
+    fn get_targets(&self) -> Vec<&str> {
+        self.objectives
+            .iter()
+            .flat_map(|objective| objective.get_targets())
+            .collect()
+    }
+
+    /// Catches objectives that could never be completed
+    fn validate(&self) -> Result<(), String> {
+        match self.get_targets().into_iter().find(|target| {
+            !self
+                .enemies
+                .iter()
+                .any(|enemy| enemy.name.as_deref() == Some(*target))
+        }) {
+            Some(target) => Err(format!("Invalid level: no enemy named '{}'", target)),
+            None => Ok(()),
+        }
+    }
+
+    fn get_survival_spawn_points(&self) -> Vec<Vec2> {
+        if self.survival_spawn_points.is_empty() {
+            self.enemies
+                .iter()
+                .map(|enemy| array_to_vec(enemy.position))
+                .collect()
+        } else {
+            self.survival_spawn_points
+                .iter()
+                .map(|point| array_to_vec(*point))
+                .collect()
+        }
+    }
+
+    /// Survival levels start empty, enemies only come in waves
+    fn into_survival_game_objects(self, difficulty: Difficulty) -> GameObjects {
+        let survival = SurvivalState::new(self.get_survival_spawn_points());
+        GameObjects {
+            enemies: vec![],
+            keys: vec![],
+            spawners: vec![],
+            objectives: vec![],
+            checkpoints: vec![],
+            survival: Some(survival),
+            ..self.into_game_objects(difficulty)
+        }
+    }
+
+    fn into_game_objects(self, difficulty: Difficulty) -> GameObjects {
         let player = crate::model::Player {
             entity: Entity {
                 position: array_to_vec(self.player.position),
@@ -59,17 +490,87 @@ impl Into<GameObjects> for Level {
             look: array_to_vec(self.player.look).normalize_or_zero(),
         };
 
+        let doors: Vec<crate::model::Door> = self.doors.iter().map(|door| door.into()).collect();
+
+        // Closed doors block like any other wall until a trigger opens them
         let walls = self
             .walls
             .iter()
-            .map(|wall| crate::model::Wall {
-                texture: wall.texture,
-                start: array_to_vec(wall.start),
-                end: array_to_vec(wall.end),
+            .map(|wall| wall.into())
+            .chain(
+                doors
+                    .iter()
+                    .filter(|door| !door.is_open)
+                    .flat_map(|door| door.walls.iter().cloned()),
+            )
+            .collect();
+
+        let triggers = self.triggers.iter().map(|trigger| trigger.into()).collect();
+
+        let teleporters = self
+            .teleporters
+            .iter()
+            .flat_map(|teleporter| teleporter.to_teleporters())
+            .collect();
+
+        let hazards = self.hazards.iter().map(|hazard| hazard.into()).collect();
+        let secrets = self.secrets.iter().map(|secret| secret.into()).collect();
+        let lights = self.lights.iter().map(|light| light.into()).collect();
+        let fog = self.fog.as_ref().map(|fog| fog.into()).unwrap_or_default();
+        let checkpoints = self
+            .checkpoints
+            .iter()
+            .map(|checkpoint| Checkpoint {
+                entity: Entity {
+                    position: array_to_vec(*checkpoint),
+                    size: CHECKPOINT_SIZE,
+                },
+                is_reached: false,
             })
             .collect();
+        let hazard_suits = self
+            .hazard_suits
+            .iter()
+            .map(|suit| HazardSuit {
+                entity: Entity {
+                    position: array_to_vec(*suit),
+                    size: HAZARD_SUIT_SIZE,
+                },
+            })
+            .collect();
+
+        // Targets spawn on every difficulty, their objectives couldn't be completed otherwise.
+        // A level that has to be cleared keeps all its enemies if none are left to fight.
+        let targets = self.get_targets();
+        let keep_all_enemies = self
+            .objectives
+            .iter()
+            .any(|objective| objective.includes_kill_all_enemies())
+            && !self.enemies.iter().any(|enemy| enemy.spawns_on(difficulty))
+            && !self
+                .spawners
+                .iter()
+                .any(|spawner| spawner.spawns_on(difficulty));
+        let enemies = self
+            .enemies
+            .iter()
+            .filter(|enemy| {
+                keep_all_enemies
+                    || enemy.spawns_on(difficulty)
+                    || enemy
+                        .name
+                        .as_deref()
+                        .is_some_and(|name| targets.contains(&name))
+            })
+            .map(|enemy| enemy.to_enemy(difficulty))
+            .collect();
 
-        let enemies = self.enemies.into_iter().map(|enemy| enemy.into()).collect();
+        let spawners = self
+            .spawners
+            .iter()
+            .filter(|spawner| spawner.spawns_on(difficulty))
+            .map(|spawner| spawner.to_spawner())
+            .collect();
 
         let keys: Vec<_> = self
             .keys
@@ -82,7 +583,14 @@ impl Into<GameObjects> for Level {
             })
             .collect();
 
-        if keys.is_empty() {
+        let objectives = if self.objectives.is_empty() {
+            crate::model::objective::Objective::default_objectives()
+        } else {
+            self.objectives.iter().map(|o| o.into()).collect()
+        };
+        let uses_default_objectives = self.objectives.is_empty();
+
+        if uses_default_objectives && keys.is_empty() {
             panic!("Invalid level: no keys");
         }
 
@@ -95,19 +603,40 @@ impl Into<GameObjects> for Level {
             })
             .collect();
 
-        if exit_triggers.is_empty() {
+        if uses_default_objectives && exit_triggers.is_empty() {
             panic!("Invalid level: no exit triggers");
         }
 
         GameObjects {
             player,
-            player_info: PlayerInfo::default(),
+            player_info: PlayerInfo::for_difficulty(difficulty),
             walls,
             enemies,
             keys,
             exit_triggers,
             decorations: vec![],
             projectiles: vec![],
+            spawners,
+            difficulty,
+            survival: None,
+            objectives,
+            level_time: 0.0,
+            triggers,
+            doors,
+            teleporters,
+            hazards,
+            hazard_suits,
+            secrets,
+            checkpoints,
+            stats: LevelStats::default(),
+            teleport_flash: 0.0,
+            message: None,
+            light_level: 1.0,
+            level_ended: false,
+            automap: Automap::default(),
+            lights,
+            fog,
+            killed_targets: vec![],
         }
     }
 }
@@ -121,15 +650,65 @@ pub fn level_exists(level_number: u32) -> bool {
     Path::new(&level_path).exists()
 }
 
-pub fn load_level(level_number: u32) -> Result<GameObjects, Box<dyn Error>> {
+pub fn load_level(
+    level_number: u32,
+    difficulty: Difficulty,
+    mode: GameMode,
+) -> Result<GameObjects, Box<dyn Error>> {
     let level_path = format!("{LEVEL_PATH}level{level_number}.json");
 
     println!("Loading level: {}", level_path);
 
     let data = read(&level_path)?;
     let level: Level = from_slice(&data)?;
-    let game_objects = level.into();
+    level.validate()?;
+    let game_objects = match mode {
+        GameMode::Campaign | GameMode::Speedrun => level.into_game_objects(difficulty),
+        GameMode::Survival => level.into_survival_game_objects(difficulty),
+    };
 
     println!("Loaded level: {}", level_path);
     Ok(game_objects)
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::from_str;
+
+    use super::*;
+
+    #[test]
+    fn test_kill_targets() {
+        let level = r#"{
+            "walls": [],
+            "player": { "position": [0.0, 0.0], "look": [0.0, 1.0] },
+            "enemies": [
+                { "position": [1.0, 1.0], "enemy_type": "Melee", "name": "Guard",
+                  "difficulties": ["Hard"] },
+                { "position": [2.0, 2.0], "enemy_type": "Melee", "difficulties": ["Hard"] }
+            ],
+            "keys": [],
+            "exit_triggers": [],
+            "objectives": [{ "AllOf": [{ "KillTarget": "Guard" }, { "Survive": 5.0 }] }]
+        }"#;
+
+        let valid: Level = from_str(level).unwrap();
+        assert!(valid.validate().is_ok());
+        let game_objects = valid.into_game_objects(Difficulty::Easy);
+        assert_eq!(game_objects.enemies.len(), 1);
+        assert_eq!(game_objects.enemies[0].name.as_deref(), Some("Guard"));
+
+        let typo: Level =
+            from_str(&level.replace("\"KillTarget\": \"Guard\"", "\"KillTarget\": \"Gaurd\""))
+                .unwrap();
+        assert!(typo.validate().is_err());
+
+        // Clearing a level needs something to kill
+        let clear: Level = from_str(&level.replace(
+            r#"{ "AllOf": [{ "KillTarget": "Guard" }, { "Survive": 5.0 }] }"#,
+            r#""KillAllEnemies""#,
+        ))
+        .unwrap();
+        assert_eq!(clear.into_game_objects(Difficulty::Easy).enemies.len(), 2);
+    }
+}
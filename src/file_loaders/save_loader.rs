@@ -0,0 +1,315 @@
+use std::{
+    error::Error,
+    fs::read,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{from_slice, to_string, Value};
+
+use crate::{
+    constants::{SAVES_PATH, SAVE_VERSION},
+    file_loaders::data_dir::{get_data_path, write_atomic},
+    model::{game_mode::GameMode, GameObjects, Wall},
+    service::id_generator::reserve_ids_up_to,
+};
+
+/// Everything needed to continue a level from where it was saved
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SaveGame {
+    /// Saves written before versioning are version 0
+    #[serde(default)]
+    pub version: u32,
+    pub level: u32,
+    pub mode: GameMode,
+    /// Time since the level started
+    pub elapsed: Duration,
+    pub game_objects: GameObjects,
+    /// State the level script keeps in `this`
+    #[serde(default)]
+    pub script_state: Option<Value>,
+    /// Console cheats were used before saving
+    #[serde(default)]
+    pub cheated: bool,
+}
+
+fn get_save_path(dir: &Path, slot: u32) -> PathBuf {
+    dir.join(format!("slot{slot}.json"))
+}
+
+fn save_game_to(dir: &Path, slot: u32, save: &SaveGame) -> Result<(), Box<dyn Error>> {
+    let save = SaveGame {
+        version: SAVE_VERSION,
+        ..save.clone()
+    };
+
+    write_atomic(&get_save_path(dir, slot), to_string(&save)?.as_bytes())?;
+    Ok(())
+}
+
+fn load_game_from(dir: &Path, slot: u32) -> Result<SaveGame, Box<dyn Error>> {
+    let data = read(get_save_path(dir, slot))?;
+    let mut save: SaveGame = from_slice(&data)?;
+    if save.version > SAVE_VERSION {
+        return Err(format!("unsupported save version {}", save.version).into());
+    }
+    if save.version < 2 {
+        tag_door_walls(&mut save.game_objects);
+    }
+
+    if let Some(max_id) = save.game_objects.enemies.iter().map(|enemy| enemy.id).max() {
+        reserve_ids_up_to(max_id);
+    }
+
+    Ok(save)
+}
+
+/// Older saves didn't record which door a wall belongs to. Closed doors
+/// added their walls after the level's own, so match from the end.
+fn tag_door_walls(game_objects: &mut GameObjects) {
+    for door in &mut game_objects.doors {
+        for door_wall in &mut door.walls {
+            let untagged = Wall {
+                door: None,
+                ..door_wall.clone()
+            };
+            door_wall.door = Some(door.name.clone());
+
+            if door.is_open {
+                continue;
+            }
+            if let Some(wall) = game_objects
+                .walls
+                .iter_mut()
+                .rev()
+                .find(|wall| **wall == untagged)
+            {
+                wall.door = Some(door.name.clone());
+            }
+        }
+    }
+}
+
+pub fn save_game(slot: u32, save: &SaveGame) -> Result<(), Box<dyn Error>> {
+    save_game_to(&get_data_path(SAVES_PATH), slot, save)
+}
+
+/// Falls back to the working directory, where older versions kept their saves
+pub fn load_game(slot: u32) -> Result<SaveGame, Box<dyn Error>> {
+    let dir = get_data_path(SAVES_PATH);
+    let legacy_dir = Path::new(SAVES_PATH);
+    if !get_save_path(&dir, slot).exists() && get_save_path(legacy_dir, slot).exists() {
+        return load_game_from(legacy_dir, slot);
+    }
+
+    load_game_from(&dir, slot)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::temp_dir,
+        fs::{remove_dir_all, write},
+        process,
+    };
+
+    use macroquad::math::{vec2, vec3};
+    use serde_json::json;
+
+    use crate::model::{
+        automap::{Automap, AutomapKey},
+        checkpoint::Checkpoint,
+        decoration::{Decoration, DecorationGraphics},
+        difficulty::Difficulty,
+        enemy::EnemyType,
+        fog::Fog,
+        key_object::KeyObject,
+        light::Light,
+        projectile::Projectile,
+        spawner::{SpawnTrigger, Spawner},
+        trigger::{Trigger, TriggerAction},
+        Door, Entity, Message, PlayerInfo, ShootingStatus, TextureId, Wall,
+    };
+
+    use super::*;
+
+    fn create_save() -> SaveGame {
+        let mut enemy = EnemyType::Ranged.to_enemy(vec2(3.0, 4.0), Difficulty::Hard);
+        enemy.attack_delay = 0.37;
+        enemy.name = Some("guard".to_string());
+        let door_wall = Wall {
+            texture: TextureId::Door,
+            start: vec2(4.0, 0.0),
+            end: vec2(4.0, 2.0),
+            is_masked: false,
+            shots_pass_through: false,
+            door: Some("gate".to_string()),
+        };
+
+        SaveGame {
+            version: SAVE_VERSION,
+            level: 2,
+            mode: GameMode::Campaign,
+            elapsed: Duration::from_millis(12_345),
+            game_objects: GameObjects {
+                player_info: PlayerInfo {
+                    shooting_status: ShootingStatus::Reloading,
+                    bullets: 3,
+                    health: 42.5,
+                    picked_up_keys: 2,
+                    ..Default::default()
+                },
+                walls: vec![
+                    Wall {
+                        texture: TextureId::Grate,
+                        start: vec2(0.0, 0.0),
+                        end: vec2(4.0, 0.0),
+                        is_masked: true,
+                        shots_pass_through: true,
+                        door: None,
+                    },
+                    door_wall.clone(),
+                ],
+                enemies: vec![enemy],
+                keys: vec![KeyObject {
+                    entity: Entity {
+                        position: vec2(1.0, 3.0),
+                        size: 0.5,
+                    },
+                }],
+                projectiles: vec![Projectile {
+                    entity: Entity {
+                        position: vec2(1.0, 1.5),
+                        size: 0.1,
+                    },
+                    direction: vec2(0.6, 0.8),
+                    damage: 7.0,
+                    texture: TextureId::Projectile,
+                }],
+                decorations: vec![Decoration {
+                    entity: Entity {
+                        position: vec2(2.0, 2.0),
+                        size: 0.5,
+                    },
+                    graphics: DecorationGraphics::Texture(TextureId::Skull),
+                    life: Some(0.25),
+                    offset: 0.1,
+                    age: 0.5,
+                    on_expire: None,
+                }],
+                spawners: vec![Spawner {
+                    position: vec2(6.0, 6.0),
+                    enemy_type: EnemyType::Melee,
+                    trigger: SpawnTrigger::KeysPickedUp(1),
+                    is_triggered: true,
+                    interval: 3.0,
+                    time_until_spawn: 1.25,
+                    max_alive: 2,
+                    remaining: Some(4),
+                    spawned_ids: vec![7],
+                }],
+                difficulty: Difficulty::Hard,
+                level_time: 12.345,
+                triggers: vec![Trigger {
+                    name: Some("ambush".to_string()),
+                    entity: Entity {
+                        position: vec2(5.0, 1.0),
+                        size: 1.0,
+                    },
+                    actions: vec![TriggerAction::CloseDoor("gate".to_string())],
+                    once: true,
+                    has_fired: true,
+                    is_player_inside: false,
+                }],
+                doors: vec![Door {
+                    name: "gate".to_string(),
+                    walls: vec![door_wall],
+                    is_open: false,
+                    is_closing: false,
+                }],
+                checkpoints: vec![Checkpoint {
+                    entity: Entity {
+                        position: vec2(2.0, 5.0),
+                        size: 0.5,
+                    },
+                    is_reached: true,
+                }],
+                message: Some(Message {
+                    text: "Checkpoint reached".to_string(),
+                    time_left: 1.5,
+                }),
+                light_level: 0.6,
+                automap: Automap {
+                    walls: vec![(vec2(0.0, 0.0), vec2(4.0, 0.0))],
+                    keys: vec![AutomapKey {
+                        position: vec2(1.0, 3.0),
+                        is_collected: true,
+                    }],
+                    exits: vec![],
+                },
+                lights: vec![Light::temporary(
+                    vec2(1.0, 1.0),
+                    vec3(1.0, 0.5, 0.0),
+                    3.0,
+                    1.0,
+                    0.4,
+                )],
+                fog: Fog {
+                    color: vec3(0.2, 0.2, 0.3),
+                    density: 0.1,
+                },
+                killed_targets: vec!["warden".to_string()],
+                ..Default::default()
+            },
+            script_state: Some(json!({ "kills": 3 })),
+            cheated: true,
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_slots() {
+        let dir = temp_dir().join(format!("save_loader_test_{}", process::id()));
+        let save = create_save();
+        let quicksave = SaveGame {
+            level: 1,
+            version: 0,
+            ..create_save()
+        };
+
+        save_game_to(&dir, 2, &save).unwrap();
+        save_game_to(&dir, 0, &quicksave).unwrap();
+        assert!(get_save_path(&dir, 2).exists());
+
+        assert_eq!(load_game_from(&dir, 2).unwrap(), save);
+        // Saving stamps the current version
+        assert_eq!(
+            load_game_from(&dir, 0).unwrap(),
+            SaveGame {
+                version: SAVE_VERSION,
+                ..quicksave
+            }
+        );
+        assert!(load_game_from(&dir, 3).is_err());
+
+        let newer = to_string(&save).unwrap().replacen(
+            &format!("\"version\":{}", SAVE_VERSION),
+            &format!("\"version\":{}", SAVE_VERSION + 1),
+            1,
+        );
+        write(get_save_path(&dir, 1), &newer).unwrap();
+        assert!(load_game_from(&dir, 1).is_err());
+
+        // Saves from before versioning get their door walls tagged
+        let legacy = newer
+            .replacen(&format!("\"version\":{},", SAVE_VERSION + 1), "", 1)
+            .replace("\"door\":\"gate\"", "\"door\":null");
+        write(get_save_path(&dir, 1), &legacy).unwrap();
+        assert_eq!(
+            load_game_from(&dir, 1).unwrap(),
+            SaveGame { version: 0, ..save }
+        );
+
+        remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,148 @@
+use std::{
+    fs::read,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{from_slice, to_string};
+
+use crate::{
+    constants::{DEFAULT_PLAYER_NAME, SETTINGS_PATH},
+    file_loaders::data_dir::{backup_corrupt_file, get_data_path, write_atomic},
+    model::difficulty::Difficulty,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Settings {
+    pub view_bob: bool,
+    /// Shows the automap in a corner while playing
+    pub minimap: bool,
+    pub difficulty: Difficulty,
+    /// Name recorded on the leaderboards
+    pub player_name: String,
+    #[serde(skip)]
+    path: PathBuf,
+}
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            view_bob: true,
+            minimap: true,
+            difficulty: Difficulty::default(),
+            player_name: std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .unwrap_or_else(|_| DEFAULT_PLAYER_NAME.to_string()),
+            path: PathBuf::new(),
+        }
+    }
+}
+impl Settings {
+    /// Loads from the data directory, importing the file older versions kept in the working directory
+    pub fn load() -> Self {
+        let path = get_data_path(SETTINGS_PATH);
+        let legacy_path = Path::new(SETTINGS_PATH);
+        let read_path = if !path.exists() && legacy_path.exists() {
+            println!("Importing settings from {}", legacy_path.display());
+            legacy_path
+        } else {
+            &path
+        };
+
+        let settings = Self {
+            path: path.clone(),
+            ..Self::load_from(read_path)
+        };
+        settings.save();
+        settings
+    }
+
+    fn load_from(path: &Path) -> Self {
+        let data = match read(path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Self::default(),
+            Err(err) => {
+                println!("Error reading {}: {}", path.display(), err);
+                return Self::default();
+            }
+        };
+
+        from_slice(&data).unwrap_or_else(|err| {
+            println!("Can't load settings from {}: {}", path.display(), err);
+            match backup_corrupt_file(path) {
+                Ok(backup_path) => println!("Moved it to {}", backup_path.display()),
+                Err(err) => println!("Error backing up settings: {}", err),
+            }
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) {
+        let json = match to_string(self) {
+            Ok(json) => json,
+            Err(err) => {
+                println!("Error serializing settings: {}", err);
+                return;
+            }
+        };
+
+        if let Err(err) = write_atomic(&self.path, json.as_bytes()) {
+            println!("Error saving settings: {}", err);
+        }
+    }
+
+    pub fn toggle_view_bob(&mut self) {
+        self.view_bob = !self.view_bob;
+        self.save();
+        println!("View bob: {}", self.view_bob);
+    }
+
+    pub fn toggle_minimap(&mut self) {
+        self.minimap = !self.minimap;
+        self.save();
+        println!("Minimap: {}", self.minimap);
+    }
+
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty;
+        self.save();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::temp_dir,
+        fs::{remove_dir_all, write},
+        process,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_load_and_save() {
+        let dir = temp_dir().join(format!("settings_test_{}", process::id()));
+        let path = dir.join(SETTINGS_PATH);
+
+        let mut settings = Settings {
+            path: path.clone(),
+            ..Settings::load_from(&path)
+        };
+        settings.set_difficulty(Difficulty::Hard);
+        settings.toggle_minimap();
+
+        let loaded = Settings::load_from(&path);
+        assert_eq!(loaded.difficulty, Difficulty::Hard);
+        assert!(!loaded.minimap);
+
+        // A corrupt file is reported and moved aside instead of being overwritten
+        write(&path, "{ not json").unwrap();
+        let loaded = Settings::load_from(&path);
+        assert_eq!(loaded.difficulty, Difficulty::default());
+        assert!(!path.exists());
+        assert_eq!(dir.read_dir().unwrap().count(), 1);
+
+        remove_dir_all(dir).unwrap();
+    }
+}
@@ -15,6 +15,8 @@ const SOUND_PATHS: [(SoundId, &str); 6] = [
     (SoundId::Escape, "escape.wav"),
 ];
 
+/// Plays nothing until its sounds are loaded
+#[derive(Default)]
 pub struct SoundManager {
     sounds: HashMap<SoundId, Sound>,
     looped_sounds: HashSet<SoundId>,
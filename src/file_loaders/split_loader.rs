@@ -0,0 +1,84 @@
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    constants::{SPLITS_PATH, START_LEVEL},
+    file_loaders::data_dir::{get_data_dir, write_atomic},
+    model::{difficulty::Difficulty, speedrun::Speedrun},
+};
+
+/// One row per level with times in seconds, the personal best columns are empty without one
+fn splits_to_csv(speedrun: &Speedrun) -> String {
+    let mut csv = "level,segment,split,best_split,delta\n".to_string();
+
+    for (i, (segment, split)) in speedrun
+        .splits
+        .iter()
+        .zip(speedrun.get_cumulative_splits())
+        .enumerate()
+    {
+        let best = speedrun
+            .best_splits
+            .get(i)
+            .map(|best| format!("{:.3}", best.as_secs_f64()))
+            .unwrap_or_default();
+        let delta = speedrun
+            .get_delta(i, split)
+            .map(|delta| format!("{:+.3}", delta))
+            .unwrap_or_default();
+
+        csv += &format!(
+            "{},{:.3},{:.3},{},{}\n",
+            START_LEVEL + i as u32,
+            segment.as_secs_f64(),
+            split.as_secs_f64(),
+            best,
+            delta
+        );
+    }
+
+    csv
+}
+
+/// Writes the splits of a finished run to the data directory, returns the file written
+pub fn export_splits(speedrun: &Speedrun, difficulty: Difficulty) -> Option<PathBuf> {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs())
+        .unwrap_or_default();
+    let path = get_data_dir()
+        .join(SPLITS_PATH)
+        .join(format!("{:?}-{}.csv", difficulty, secs).to_lowercase());
+
+    match write_atomic(&path, splits_to_csv(speedrun).as_bytes()) {
+        Ok(()) => Some(path),
+        Err(err) => {
+            println!("Error exporting splits: {}", err);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_splits_to_csv() {
+        let speedrun = Speedrun {
+            splits: vec![Duration::from_millis(30_500), Duration::from_millis(40_250)],
+            best_splits: vec![Duration::from_secs(31)],
+        };
+
+        assert_eq!(
+            splits_to_csv(&speedrun),
+            "level,segment,split,best_split,delta\n\
+             1,30.500,30.500,31.000,-0.500\n\
+             2,40.250,70.750,,\n"
+        );
+    }
+}
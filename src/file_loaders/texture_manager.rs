@@ -1,8 +1,18 @@
 use std::{collections::HashMap, fs::read};
 
-use macroquad::texture::{FilterMode, Texture2D};
+use macroquad::{
+    color::Color,
+    texture::{FilterMode, Image, Texture2D},
+};
 
-use crate::{constants::TEXTURE_PATH, model::TextureId};
+use crate::{
+    constants::{
+        ENEMY_CORPSE_HEIGHT, ENEMY_DEATH_ANIMATION_FRAMES, ENEMY_DEATH_DARKEN, GRATE_BAR_WIDTH,
+        GRATE_COLUMNS, GRATE_ROWS, TEXTURE_PATH, WINDOW_FRAME_COLOR, WINDOW_FRAME_WIDTH,
+        WINDOW_GLASS_COLOR, WINDOW_TEXTURE_SIZE,
+    },
+    model::TextureId,
+};
 
 const TEXTURE_PATHS: [(TextureId, &str); 49] = [
     (TextureId::Stone, "stone.png"),
@@ -56,8 +66,47 @@ const TEXTURE_PATHS: [(TextureId, &str); 49] = [
     (TextureId::TextFindTheKeys, "text/find_exit.png"),
 ];
 
+/// The grate is cut out of this texture
+const GRATE_SOURCE: &str = "metal.png";
+
+/// Death animations and corpses are generated by collapsing the first frame of each enemy
+const DEATH_ANIMATION_SOURCES: [(&str, [TextureId; ENEMY_DEATH_ANIMATION_FRAMES], TextureId); 3] = [
+    (
+        "enemy/melee/enemy1.png",
+        [
+            TextureId::EnemyDeath1,
+            TextureId::EnemyDeath2,
+            TextureId::EnemyDeath3,
+            TextureId::EnemyDeath4,
+        ],
+        TextureId::EnemyCorpse,
+    ),
+    (
+        "enemy/ranged/enemy1.png",
+        [
+            TextureId::RangedEnemyDeath1,
+            TextureId::RangedEnemyDeath2,
+            TextureId::RangedEnemyDeath3,
+            TextureId::RangedEnemyDeath4,
+        ],
+        TextureId::RangedEnemyCorpse,
+    ),
+    (
+        "enemy/meleeSlow/enemy1.png",
+        [
+            TextureId::MeleeSlowEnemyDeath1,
+            TextureId::MeleeSlowEnemyDeath2,
+            TextureId::MeleeSlowEnemyDeath3,
+            TextureId::MeleeSlowEnemyDeath4,
+        ],
+        TextureId::MeleeSlowEnemyCorpse,
+    ),
+];
+
 pub struct TextureManager {
     textures: HashMap<TextureId, Texture2D>,
+    /// White copies of the textures keeping only their alpha, tinted to draw fog over them
+    silhouettes: HashMap<TextureId, Texture2D>,
 }
 impl TextureManager {
     fn create_default_texture() -> Texture2D {
@@ -105,12 +154,129 @@ impl TextureManager {
         }
     }
 
+    /// Squashes the image towards its bottom edge, `progress` goes from 0.0 (unchanged) to 1.0 (corpse)
+    fn create_collapsed_texture(source: &Image, progress: f32) -> Texture2D {
+        let width = source.width as u32;
+        let height = source.height as u32;
+        let scale = 1.0 - progress * (1.0 - ENEMY_CORPSE_HEIGHT);
+        let visible_height = ((height as f32 * scale) as u32).clamp(1, height);
+        let brightness = 1.0 - progress * (1.0 - ENEMY_DEATH_DARKEN);
+
+        let mut image =
+            Image::gen_image_color(source.width, source.height, Color::new(0.0, 0.0, 0.0, 0.0));
+        for y in 0..visible_height {
+            let source_y = y * height / visible_height;
+            for x in 0..width {
+                let pixel = source.get_pixel(x, source_y);
+                let color = Color::new(
+                    pixel.r * brightness,
+                    pixel.g * brightness * brightness,
+                    pixel.b * brightness * brightness,
+                    pixel.a,
+                );
+                image.set_pixel(x, height - visible_height + y, color);
+            }
+        }
+
+        let texture = Texture2D::from_image(&image);
+        texture.set_filter(FilterMode::Nearest);
+        texture
+    }
+
+    fn create_death_textures(textures: &mut HashMap<TextureId, Texture2D>) {
+        for (path, frames, corpse) in DEATH_ANIMATION_SOURCES {
+            let full_path = TEXTURE_PATH.to_string() + path;
+            let source =
+                match read(full_path).map(|bytes| Image::from_file_with_format(&bytes, None)) {
+                    Ok(Ok(image)) => image,
+                    _ => {
+                        println!("Error creating death animation from '{path}'");
+                        continue;
+                    }
+                };
+
+            for (i, frame) in frames.iter().enumerate() {
+                let progress = (i + 1) as f32 / (frames.len() + 1) as f32;
+                textures.insert(*frame, Self::create_collapsed_texture(&source, progress));
+            }
+            textures.insert(corpse, Self::create_collapsed_texture(&source, 1.0));
+        }
+    }
+
+    /// Cuts see-through holes between the bars of a grid
+    fn create_grate_texture(source: &Image) -> Texture2D {
+        let mut image = source.clone();
+        let cell_width = (image.width as u32 / GRATE_COLUMNS).max(1);
+        let cell_height = (image.height as u32 / GRATE_ROWS).max(1);
+        let bar_width = (cell_width as f32 * GRATE_BAR_WIDTH) as u32;
+        let bar_height = (cell_height as f32 * GRATE_BAR_WIDTH) as u32;
+
+        for y in 0..image.height as u32 {
+            for x in 0..image.width as u32 {
+                if x % cell_width >= bar_width && y % cell_height >= bar_height {
+                    image.set_pixel(x, y, Color::new(0.0, 0.0, 0.0, 0.0));
+                }
+            }
+        }
+
+        let texture = Texture2D::from_image(&image);
+        texture.set_filter(FilterMode::Nearest);
+        texture
+    }
+
+    /// Tinted glass in a solid frame
+    fn create_window_texture() -> Texture2D {
+        let mut image =
+            Image::gen_image_color(WINDOW_TEXTURE_SIZE, WINDOW_TEXTURE_SIZE, WINDOW_FRAME_COLOR);
+        for y in WINDOW_FRAME_WIDTH..WINDOW_TEXTURE_SIZE - WINDOW_FRAME_WIDTH {
+            for x in WINDOW_FRAME_WIDTH..WINDOW_TEXTURE_SIZE - WINDOW_FRAME_WIDTH {
+                image.set_pixel(x as u32, y as u32, WINDOW_GLASS_COLOR);
+            }
+        }
+
+        let texture = Texture2D::from_image(&image);
+        texture.set_filter(FilterMode::Nearest);
+        texture
+    }
+
+    fn create_masked_textures(textures: &mut HashMap<TextureId, Texture2D>) {
+        let full_path = TEXTURE_PATH.to_string() + GRATE_SOURCE;
+        match read(full_path).map(|bytes| Image::from_file_with_format(&bytes, None)) {
+            Ok(Ok(image)) => {
+                textures.insert(TextureId::Grate, Self::create_grate_texture(&image));
+            }
+            _ => println!("Error creating grate from '{GRATE_SOURCE}'"),
+        }
+        textures.insert(TextureId::Window, Self::create_window_texture());
+    }
+
+    fn create_silhouette(texture: &Texture2D) -> Texture2D {
+        let mut image = texture.get_texture_data();
+        for pixel in image.get_image_data_mut() {
+            *pixel = [255, 255, 255, pixel[3]];
+        }
+
+        let silhouette = Texture2D::from_image(&image);
+        silhouette.set_filter(FilterMode::Nearest);
+        silhouette
+    }
+
     pub fn load() -> Self {
         let mut textures = HashMap::new();
         textures.insert(TextureId::Debug, Self::create_default_texture());
         Self::load_multiple_textures(&mut textures, FilterMode::Nearest, &TEXTURE_PATHS);
+        Self::create_death_textures(&mut textures);
+        Self::create_masked_textures(&mut textures);
+
+        let silhouettes = textures
+            .iter()
+            .map(|(id, texture)| (*id, Self::create_silhouette(texture)))
+            .collect();
 
-        Self { textures }
+        Self {
+            textures,
+            silhouettes,
+        }
     }
 
     pub fn get_texture(&self, texture: TextureId) -> &Texture2D {
@@ -123,4 +289,11 @@ impl TextureManager {
             .get(&TextureId::Debug)
             .expect("Debug texture not found")
     }
+
+    pub fn get_silhouette(&self, texture: TextureId) -> &Texture2D {
+        self.silhouettes
+            .get(&texture)
+            .or_else(|| self.silhouettes.get(&TextureId::Debug))
+            .expect("Debug silhouette not found")
+    }
 }
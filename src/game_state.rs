@@ -1,57 +1,150 @@
-use std::time::{Duration, Instant};
+use std::{
+    mem::take,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use macroquad::{
-    input::{is_key_released, KeyCode},
+    input::{is_key_down, is_key_pressed, is_key_released, KeyCode},
     time::get_frame_time,
 };
 
 use crate::{
-    constants::START_LEVEL,
+    console::{Console, ConsoleAction},
+    constants::{
+        AUTOMAP_KEY, DEBUG_MAP_KEY, LEADERBOARD_KEY, LOAD_SLOT_MODIFIER_KEY, MINIMAP_TOGGLE_KEY,
+        QUICKLOAD_KEY, QUICKSAVE_KEY, QUICKSAVE_SLOT, RESTART_LEVEL_KEY, SAVE_MESSAGE_DURATION,
+        SAVE_SLOT_KEYS, SAVE_VERSION, START_LEVEL, TIMER_MAX_FRAME_TIME, VIEW_BOB_TOGGLE_KEY,
+    },
     controller::{
         handle_events, handle_input, is_game_over, is_game_won, next_game_step, play_sounds,
         reset_state,
     },
-    draw::draw_game,
+    draw::{
+        automap_display::{draw_automap, AutomapView},
+        console_display::draw_console_display,
+        debug_map::draw_debug_map,
+        draw_game,
+        speedrun_display::draw_speedrun_display,
+        wall::cast_rays,
+        Camera,
+    },
     file_loaders::{
-        completion_time_loader::{load_best_for_level, save_best_for_level},
+        completion_time_loader::BestCompletionTimes,
         level_loader::{level_exists, load_level},
+        save_loader::{load_game, save_game, SaveGame},
+        settings_loader::Settings,
         sound_manager::SoundManager,
+        split_loader::export_splits,
         texture_manager::TextureManager,
     },
     input::get_input,
-    model::{GameObjects, SoundId},
-    renderer::{render_drawables, render_game_over, render_game_won, render_level_won},
+    model::{
+        difficulty::Difficulty,
+        game_mode::GameMode,
+        leaderboard::{Leaderboard, LeaderboardEntry},
+        level_stats::LevelStats,
+        speedrun::Speedrun,
+        survival::SurvivalResult,
+        GameEvent, GameObjects, Message, SoundId,
+    },
+    renderer::{
+        render_drawables, render_game_over, render_game_won, render_leaderboard, render_level_won,
+        render_select_difficulty,
+    },
+    scripting::LevelScript,
+    service::{automap::update_automap, stats::finish_level_stats},
 };
 
 pub struct ResourceManager {
     texture_manager: TextureManager,
     sound_manager: SoundManager,
+    settings: Settings,
+    completion_times: BestCompletionTimes,
 }
 impl ResourceManager {
     async fn load() -> Self {
         Self {
             texture_manager: TextureManager::load(),
             sound_manager: SoundManager::load().await,
+            settings: Settings::load(),
+            completion_times: BestCompletionTimes::load(),
         }
     }
 }
 
 pub struct GameContext {
     game_objects: GameObjects,
-    start_time: Instant,
+    /// Game time spent in the level, it stands still in menus and while loading
+    time: Duration,
     level: u32,
+    mode: GameMode,
+    script: Option<LevelScript>,
+    /// Snapshot taken at the last checkpoint the player reached
+    checkpoint: Option<SaveGame>,
+    /// Set once the run continued from a checkpoint or a save, its time isn't a full run
+    used_checkpoint: bool,
+    /// Set once a console cheat was used, the run doesn't count towards any record
+    cheated: bool,
+    speedrun: Option<Speedrun>,
+    /// Shows the full screen automap instead of the minimap
+    is_automap_open: bool,
 }
 impl GameContext {
-    fn load() -> Self {
+    fn load(difficulty: Difficulty, mode: GameMode, speedrun: Option<Speedrun>) -> Self {
         Self {
-            game_objects: load_level(START_LEVEL).expect("Can't find start level"),
-            start_time: Instant::now(),
+            game_objects: load_level(START_LEVEL, difficulty, mode)
+                .expect("Can't find start level"),
+            time: Duration::ZERO,
             level: START_LEVEL,
+            mode,
+            script: LevelScript::load(START_LEVEL),
+            checkpoint: None,
+            used_checkpoint: false,
+            cheated: false,
+            speedrun,
+            is_automap_open: false,
+        }
+    }
+
+    fn to_save(&self) -> SaveGame {
+        SaveGame {
+            version: SAVE_VERSION,
+            level: self.level,
+            mode: self.mode,
+            elapsed: self.time,
+            game_objects: self.game_objects.clone(),
+            script_state: self.script.as_ref().and_then(|script| script.get_state()),
+            cheated: self.cheated,
+        }
+    }
+
+    fn from_save(save: SaveGame, checkpoint: Option<SaveGame>) -> Self {
+        let mut script = LevelScript::load(save.level);
+        if let (Some(script), Some(state)) = (script.as_mut(), save.script_state.as_ref()) {
+            script.set_state(state);
+        }
+
+        Self {
+            game_objects: save.game_objects,
+            time: save.elapsed,
+            level: save.level,
+            mode: save.mode,
+            script,
+            checkpoint,
+            used_checkpoint: true,
+            cheated: save.cheated,
+            speedrun: None,
+            is_automap_open: false,
         }
     }
 }
 
 pub enum GameState {
+    SelectDifficulty {
+        selected: Difficulty,
+        mode: GameMode,
+        resource_manager: ResourceManager,
+    },
     Running {
         context: Box<GameContext>,
         resource_manager: ResourceManager,
@@ -60,10 +153,18 @@ pub enum GameState {
         context: Box<GameContext>,
         time_to_complete: Duration,
         best_time_to_complete: Duration,
+        stats: LevelStats,
+        leaderboard: Leaderboard,
         resource_manager: ResourceManager,
     },
+    Leaderboard {
+        leaderboard: Leaderboard,
+        /// Screen to go back to
+        previous: Box<GameState>,
+    },
     GameOver {
         context: Box<GameContext>,
+        survival_result: Option<SurvivalResult>,
         resource_manager: ResourceManager,
     },
     GameWon {
@@ -74,35 +175,247 @@ pub enum GameState {
 }
 impl GameState {
     pub async fn initialise() -> Self {
-        Self::Running {
-            context: Box::new(GameContext::load()),
-            resource_manager: ResourceManager::load().await,
+        let resource_manager = ResourceManager::load().await;
+        Self::SelectDifficulty {
+            selected: resource_manager.settings.difficulty,
+            mode: GameMode::default(),
+            resource_manager,
         }
     }
+
+    fn get_resource_manager(&self) -> &ResourceManager {
+        match self {
+            Self::SelectDifficulty {
+                resource_manager, ..
+            }
+            | Self::Running {
+                resource_manager, ..
+            }
+            | Self::LevelWon {
+                resource_manager, ..
+            }
+            | Self::GameOver {
+                resource_manager, ..
+            }
+            | Self::GameWon {
+                resource_manager, ..
+            } => resource_manager,
+            Self::Leaderboard { previous, .. } => previous.get_resource_manager(),
+        }
+    }
+}
+
+fn show_save_message(game_objects: &mut GameObjects, text: String) {
+    println!("{}", text);
+    game_objects.message = Some(Message {
+        text,
+        time_left: SAVE_MESSAGE_DURATION,
+    });
+}
+
+fn save_context(context: &mut GameContext, slot: u32) {
+    let text = match save_game(slot, &context.to_save()) {
+        Ok(()) => format!("Saved to slot {}", slot),
+        Err(err) => format!("Error saving to slot {}: {}", slot, err),
+    };
+    show_save_message(&mut context.game_objects, text);
+}
+
+fn load_context(mut context: Box<GameContext>, slot: u32) -> Box<GameContext> {
+    let save = match load_game(slot) {
+        Ok(save) => save,
+        Err(err) => {
+            show_save_message(
+                &mut context.game_objects,
+                format!("Error loading slot {}: {}", slot, err),
+            );
+            return context;
+        }
+    };
+
+    let mut context = Box::new(GameContext::from_save(save, None));
+    show_save_message(&mut context.game_objects, format!("Loaded slot {}", slot));
+    context
+}
+
+/// Quicksave and quickload, or save and load one of the numbered slots
+fn handle_save_keys(mut context: Box<GameContext>) -> Box<GameContext> {
+    if is_key_pressed(QUICKSAVE_KEY) {
+        save_context(&mut context, QUICKSAVE_SLOT);
+    } else if is_key_pressed(QUICKLOAD_KEY) {
+        return load_context(context, QUICKSAVE_SLOT);
+    }
+
+    let Some(slot) = SAVE_SLOT_KEYS
+        .iter()
+        .position(|key| is_key_pressed(*key))
+        .map(|index| index as u32 + 1)
+    else {
+        return context;
+    };
+
+    if is_key_down(LOAD_SLOT_MODIFIER_KEY) {
+        load_context(context, slot)
+    } else {
+        save_context(&mut context, slot);
+        context
+    }
+}
+
+fn finish_survival(
+    context: &GameContext,
+    completion_times: &mut BestCompletionTimes,
+) -> Option<SurvivalResult> {
+    let survival = context.game_objects.survival.as_ref()?;
+    let difficulty = context.game_objects.difficulty;
+    let score = survival.get_score();
+    if !context.cheated {
+        completion_times.save_high_score(context.level, difficulty, score);
+    }
+
+    Some(SurvivalResult {
+        score,
+        wave: survival.wave,
+        high_scores: completion_times.get_high_scores(context.level, difficulty),
+    })
+}
+
+/// Stores a new personal best and exports the splits, returns the run time and the best one
+fn finish_speedrun(
+    speedrun: &Speedrun,
+    difficulty: Difficulty,
+    cheated: bool,
+    completion_times: &mut BestCompletionTimes,
+) -> (Duration, Duration) {
+    let total = speedrun.get_total(Duration::ZERO);
+    if speedrun.is_personal_best() && !cheated {
+        completion_times.save_best_splits(difficulty, speedrun.get_cumulative_splits());
+    }
+    if let Some(path) = export_splits(speedrun, difficulty) {
+        println!("Splits saved to {}", path.display());
+    }
+
+    let best = completion_times
+        .get_best_splits(difficulty)
+        .last()
+        .copied()
+        .unwrap_or(total);
+    (total, best)
 }
 
-fn level_complete(context: Box<GameContext>, resource_manager: ResourceManager) -> GameState {
+fn level_complete(
+    mut context: Box<GameContext>,
+    mut resource_manager: ResourceManager,
+) -> GameState {
     let next_level = context.level + 1;
-    let duration = Instant::now().duration_since(context.start_time);
-    save_best_for_level(context.level, &duration);
+    let difficulty = context.game_objects.difficulty;
+    let duration = context.time;
+    if let Some(speedrun) = context.speedrun.as_mut() {
+        speedrun.splits.push(duration);
+    }
+    let completion_times = &mut resource_manager.completion_times;
+    if !context.cheated {
+        completion_times.save_best_for_level(
+            context.level,
+            difficulty,
+            &duration,
+            context.used_checkpoint,
+        );
+    }
+    let best_time_to_complete =
+        completion_times.get_best_for_level(context.level, difficulty, context.used_checkpoint);
+    let stats = finish_level_stats(&context.game_objects);
+    let leaderboard = if context.used_checkpoint || context.cheated {
+        completion_times.get_leaderboard(context.level, difficulty)
+    } else {
+        let entry = LeaderboardEntry {
+            name: resource_manager.settings.player_name.clone(),
+            date: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|since_epoch| since_epoch.as_secs())
+                .unwrap_or_default(),
+            difficulty,
+            time: duration,
+            stats,
+        };
+        completion_times.save_leaderboard_entry(context.level, entry)
+    };
+
     if level_exists(next_level) {
-        let game_objects = load_level(next_level).expect("Error loading level");
+        let game_objects =
+            load_level(next_level, difficulty, context.mode).expect("Error loading level");
+        let context = Box::new(GameContext {
+            game_objects,
+            time: Duration::ZERO,
+            level: next_level,
+            script: LevelScript::load(next_level),
+            checkpoint: None,
+            used_checkpoint: false,
+            ..*context
+        });
+
+        // Speedruns go straight on to the next level
+        if context.speedrun.is_some() {
+            return GameState::Running {
+                context,
+                resource_manager,
+            };
+        }
 
         GameState::LevelWon {
-            context: Box::new(GameContext {
-                game_objects,
-                level: next_level,
-                ..*context
-            }),
+            context,
             time_to_complete: duration,
+            best_time_to_complete,
+            resource_manager,
+            stats,
+            leaderboard,
+        }
+    } else if let Some(speedrun) = context.speedrun.as_ref() {
+        let (time_to_complete, best_time_to_complete) = finish_speedrun(
+            speedrun,
+            difficulty,
+            context.cheated,
+            &mut resource_manager.completion_times,
+        );
+
+        GameState::GameWon {
+            time_to_complete,
+            best_time_to_complete,
             resource_manager,
-            best_time_to_complete: load_best_for_level(context.level),
         }
     } else {
         GameState::GameWon {
             time_to_complete: duration,
+            best_time_to_complete,
             resource_manager,
-            best_time_to_complete: load_best_for_level(context.level),
+        }
+    }
+}
+
+/// Starts another level from the console, such runs don't count towards records
+fn warp(context: Box<GameContext>, level: u32, console: &mut Console) -> Box<GameContext> {
+    if !level_exists(level) {
+        console.print(format!("No level {}", level));
+        return context;
+    }
+
+    match load_level(level, context.game_objects.difficulty, context.mode) {
+        Ok(game_objects) => {
+            console.print(format!("Warped to level {}", level));
+            Box::new(GameContext {
+                game_objects,
+                time: Duration::ZERO,
+                level,
+                script: LevelScript::load(level),
+                checkpoint: None,
+                used_checkpoint: true,
+                speedrun: None,
+                ..*context
+            })
+        }
+        Err(err) => {
+            console.print(format!("Error loading level {}: {}", level, err));
+            context
         }
     }
 }
@@ -110,28 +423,122 @@ fn level_complete(context: Box<GameContext>, resource_manager: ResourceManager)
 async fn normal_run(
     mut context: Box<GameContext>,
     mut resource_manager: ResourceManager,
+    console: &mut Console,
 ) -> (GameState, bool) {
-    let delta = get_frame_time();
-    let time_from_start = context.start_time.elapsed();
+    let delta = get_frame_time() * console.cvars.timescale;
+    context.time += Duration::from_secs_f32(delta.min(TIMER_MAX_FRAME_TIME));
+    let time_from_start = context.time;
 
     reset_state(&mut context.game_objects);
 
-    let input = get_input();
+    let (console_events, console_actions) = match console.handle_keys() {
+        Some(line) => console.execute_all(&line, &mut context.game_objects),
+        None => (vec![], vec![]),
+    };
+    if console.take_cheated() {
+        context.cheated = true;
+    }
+
+    // Keys typed into the console don't control the game
+    let input = if console.is_open {
+        vec![]
+    } else {
+        if is_key_pressed(VIEW_BOB_TOGGLE_KEY) {
+            resource_manager.settings.toggle_view_bob();
+        }
+        if is_key_pressed(DEBUG_MAP_KEY) {
+            console.cvars.debug_map = !console.cvars.debug_map;
+        }
+        if is_key_pressed(AUTOMAP_KEY) {
+            context.is_automap_open = !context.is_automap_open;
+        }
+        if is_key_pressed(MINIMAP_TOGGLE_KEY) {
+            resource_manager.settings.toggle_minimap();
+        }
+        // Loading a save would rewind the speedrun clock
+        if context.speedrun.is_none() {
+            context = handle_save_keys(context);
+        }
+        get_input()
+    };
     (
         context.game_objects.player,
         context.game_objects.player_info,
-    ) = handle_input(&context.game_objects, &input, delta);
+    ) = handle_input(&context.game_objects, &input, &console.cvars, delta);
 
-    let events;
+    let mut events;
     (context.game_objects, events) = next_game_step(context.game_objects, delta);
-    handle_events(
+    events.extend(console_events);
+    if let Some(script) = context.script.as_mut() {
+        let script_events = script.run_hooks(&context.game_objects, &events, delta);
+        events.extend(script_events);
+    }
+    let mut caused_events = handle_events(
         &resource_manager.sound_manager,
         &mut context.game_objects,
         &events,
     );
+    // Kills by explosions only happen while the events above are handled
+    if let Some(script) = context.script.as_mut() {
+        while !caused_events.is_empty() {
+            let script_events = script.run_event_hooks(&context.game_objects, &caused_events);
+            caused_events = handle_events(
+                &resource_manager.sound_manager,
+                &mut context.game_objects,
+                &script_events,
+            );
+        }
+    }
+    console.apply_cheats(&mut context.game_objects);
+    for action in console_actions {
+        context = match action {
+            ConsoleAction::Warp(level) => warp(context, level, console),
+        };
+    }
+    // A snapshot of a player killed on the same frame would restore straight into game over
+    if events
+        .iter()
+        .any(|event| matches!(event, GameEvent::CheckpointReached))
+        && !is_game_over(&context.game_objects)
+    {
+        context.checkpoint = Some(context.to_save());
+    }
     play_sounds(&mut resource_manager.sound_manager, &context.game_objects);
 
-    let to_draw = draw_game(&context.game_objects, &time_from_start);
+    let ray_hits = cast_rays(
+        &Camera::for_player(&context.game_objects.player),
+        &context.game_objects.walls,
+    );
+    let hit_walls: Vec<_> = ray_hits
+        .iter()
+        .flat_map(|(_, hits)| hits.iter().map(|hit| hit.wall_index))
+        .collect();
+    context.game_objects.automap = update_automap(
+        take(&mut context.game_objects.automap),
+        &context.game_objects,
+        &hit_walls,
+    );
+
+    let mut to_draw = draw_game(
+        &context.game_objects,
+        &ray_hits,
+        &time_from_start,
+        &resource_manager.settings,
+    );
+    if let Some(speedrun) = &context.speedrun {
+        to_draw.push(draw_speedrun_display(speedrun, context.time));
+    }
+    if context.is_automap_open {
+        to_draw.push(draw_automap(&context.game_objects, AutomapView::FullScreen));
+    } else if resource_manager.settings.minimap {
+        to_draw.push(draw_automap(&context.game_objects, AutomapView::Minimap));
+    }
+    if console.cvars.debug_map {
+        to_draw.push(draw_debug_map(&context.game_objects, &ray_hits));
+    }
+    if console.is_open {
+        to_draw.push(draw_console_display(console));
+    }
 
     render_drawables(&resource_manager.texture_manager, &to_draw).await;
 
@@ -139,6 +546,7 @@ async fn normal_run(
         resource_manager.sound_manager.stop_all();
         resource_manager.sound_manager.play(SoundId::Lose);
         GameState::GameOver {
+            survival_result: finish_survival(&context, &mut resource_manager.completion_times),
             context,
             resource_manager,
         }
@@ -158,24 +566,44 @@ async fn normal_run(
 
 async fn game_over_run(
     context: Box<GameContext>,
+    survival_result: Option<SurvivalResult>,
     resource_manager: ResourceManager,
 ) -> (GameState, bool) {
-    render_game_over().await;
+    render_game_over(survival_result.as_ref(), context.checkpoint.is_some()).await;
     if is_key_released(KeyCode::N) {
         (
             GameState::GameOver {
                 context,
+                survival_result,
                 resource_manager,
             },
             true,
         )
-    } else if is_key_released(KeyCode::Y) {
-        let game_objects = load_level(context.level);
+    } else if let (true, Some(checkpoint)) =
+        (is_key_released(KeyCode::Y), context.checkpoint.as_ref())
+    {
+        let mut restored = GameContext::from_save(checkpoint.clone(), Some(checkpoint.clone()));
+        // Speedruns keep the clock running through retries
+        if context.speedrun.is_some() {
+            restored.time = context.time;
+            restored.speedrun = context.speedrun.clone();
+        }
+
+        (
+            GameState::Running {
+                context: Box::new(restored),
+                resource_manager,
+            },
+            false,
+        )
+    } else if is_key_released(KeyCode::Y) || is_key_released(RESTART_LEVEL_KEY) {
+        let game_objects = load_level(context.level, context.game_objects.difficulty, context.mode);
         if game_objects.is_err() {
             println!("Error reloading level {}", context.level);
             return (
                 GameState::GameOver {
                     context,
+                    survival_result,
                     resource_manager,
                 },
                 false,
@@ -186,8 +614,15 @@ async fn game_over_run(
             GameState::Running {
                 context: Box::new(GameContext {
                     game_objects: game_objects.unwrap(),
-                    start_time: Instant::now(),
-                    level: context.level,
+                    time: if context.speedrun.is_some() {
+                        context.time
+                    } else {
+                        Duration::ZERO
+                    },
+                    script: LevelScript::load(context.level),
+                    checkpoint: None,
+                    used_checkpoint: false,
+                    ..*context
                 }),
                 resource_manager,
             },
@@ -197,6 +632,7 @@ async fn game_over_run(
         (
             GameState::GameOver {
                 context,
+                survival_result,
                 resource_manager,
             },
             false,
@@ -221,8 +657,9 @@ async fn game_won_run(
         )
     } else if is_key_released(KeyCode::Y) {
         (
-            GameState::Running {
-                context: Box::new(GameContext::load()),
+            GameState::SelectDifficulty {
+                selected: resource_manager.settings.difficulty,
+                mode: GameMode::default(),
                 resource_manager,
             },
             false,
@@ -243,26 +680,48 @@ async fn level_won_run(
     context: Box<GameContext>,
     time_to_complete: Duration,
     best_time_to_complete: Duration,
+    stats: LevelStats,
+    leaderboard: Leaderboard,
     resource_manager: ResourceManager,
 ) -> (GameState, bool) {
-    render_level_won(time_to_complete, best_time_to_complete).await;
+    render_level_won(
+        time_to_complete,
+        best_time_to_complete,
+        &stats,
+        leaderboard.new_record,
+    )
+    .await;
     if is_key_released(KeyCode::N) {
         (
             GameState::LevelWon {
                 context,
                 time_to_complete,
                 best_time_to_complete,
+                stats,
+                leaderboard,
                 resource_manager,
             },
             true,
         )
+    } else if is_key_pressed(LEADERBOARD_KEY) {
+        (
+            GameState::Leaderboard {
+                leaderboard: leaderboard.clone(),
+                previous: Box::new(GameState::LevelWon {
+                    context,
+                    time_to_complete,
+                    best_time_to_complete,
+                    stats,
+                    leaderboard,
+                    resource_manager,
+                }),
+            },
+            false,
+        )
     } else if is_key_released(KeyCode::Y) {
         (
             GameState::Running {
-                context: Box::new(GameContext {
-                    start_time: Instant::now(),
-                    ..*context
-                }),
+                context,
                 resource_manager,
             },
             false,
@@ -273,6 +732,8 @@ async fn level_won_run(
                 context,
                 time_to_complete,
                 best_time_to_complete,
+                stats,
+                leaderboard,
                 resource_manager,
             },
             false,
@@ -280,16 +741,142 @@ async fn level_won_run(
     }
 }
 
-pub async fn run(state: GameState) -> (GameState, bool) {
+async fn select_difficulty_run(
+    selected: Difficulty,
+    mode: GameMode,
+    mut resource_manager: ResourceManager,
+) -> (GameState, bool) {
+    render_select_difficulty(selected, mode).await;
+    if is_key_pressed(KeyCode::Up) || is_key_pressed(KeyCode::W) {
+        (
+            GameState::SelectDifficulty {
+                selected: selected.previous(),
+                mode,
+                resource_manager,
+            },
+            false,
+        )
+    } else if is_key_pressed(KeyCode::Down) || is_key_pressed(KeyCode::S) {
+        (
+            GameState::SelectDifficulty {
+                selected: selected.next(),
+                mode,
+                resource_manager,
+            },
+            false,
+        )
+    } else if is_key_pressed(KeyCode::Left)
+        || is_key_pressed(KeyCode::Right)
+        || is_key_pressed(KeyCode::A)
+        || is_key_pressed(KeyCode::D)
+    {
+        (
+            GameState::SelectDifficulty {
+                selected,
+                mode: mode.next(),
+                resource_manager,
+            },
+            false,
+        )
+    } else if is_key_pressed(LEADERBOARD_KEY) {
+        (
+            GameState::Leaderboard {
+                leaderboard: resource_manager
+                    .completion_times
+                    .get_leaderboard(START_LEVEL, selected),
+                previous: Box::new(GameState::SelectDifficulty {
+                    selected,
+                    mode,
+                    resource_manager,
+                }),
+            },
+            false,
+        )
+    } else if is_key_released(KeyCode::Enter) {
+        resource_manager.settings.set_difficulty(selected);
+        (
+            GameState::Running {
+                context: Box::new(GameContext::load(
+                    selected,
+                    mode,
+                    (mode == GameMode::Speedrun).then(|| {
+                        Speedrun::new(resource_manager.completion_times.get_best_splits(selected))
+                    }),
+                )),
+                resource_manager,
+            },
+            false,
+        )
+    } else {
+        (
+            GameState::SelectDifficulty {
+                selected,
+                mode,
+                resource_manager,
+            },
+            false,
+        )
+    }
+}
+
+async fn leaderboard_run(leaderboard: Leaderboard, previous: Box<GameState>) -> (GameState, bool) {
+    render_leaderboard(&leaderboard).await;
+    if is_key_pressed(LEADERBOARD_KEY) {
+        return (*previous, false);
+    }
+
+    let level = if is_key_pressed(KeyCode::Left) || is_key_pressed(KeyCode::A) {
+        leaderboard.level.saturating_sub(1).max(START_LEVEL)
+    } else if is_key_pressed(KeyCode::Right) || is_key_pressed(KeyCode::D) {
+        Some(leaderboard.level + 1)
+            .filter(|level| level_exists(*level))
+            .unwrap_or(leaderboard.level)
+    } else {
+        leaderboard.level
+    };
+
+    let difficulty = if is_key_pressed(KeyCode::Up) || is_key_pressed(KeyCode::W) {
+        leaderboard.difficulty.previous()
+    } else if is_key_pressed(KeyCode::Down) || is_key_pressed(KeyCode::S) {
+        leaderboard.difficulty.next()
+    } else {
+        leaderboard.difficulty
+    };
+
+    let leaderboard = if level == leaderboard.level && difficulty == leaderboard.difficulty {
+        leaderboard
+    } else {
+        previous
+            .get_resource_manager()
+            .completion_times
+            .get_leaderboard(level, difficulty)
+    };
+
+    (
+        GameState::Leaderboard {
+            leaderboard,
+            previous,
+        },
+        false,
+    )
+}
+
+pub async fn run(state: GameState, console: &mut Console) -> (GameState, bool) {
     match state {
+        GameState::SelectDifficulty {
+            selected,
+            mode,
+            resource_manager,
+        } => select_difficulty_run(selected, mode, resource_manager).await,
         GameState::Running {
             context,
             resource_manager,
-        } => normal_run(context, resource_manager).await,
+        } => normal_run(context, resource_manager, console).await,
         GameState::GameOver {
             context,
+            survival_result,
             resource_manager,
-        } => game_over_run(context, resource_manager).await,
+        } => game_over_run(context, survival_result, resource_manager).await,
         GameState::GameWon {
             time_to_complete,
             best_time_to_complete,
@@ -299,15 +886,23 @@ pub async fn run(state: GameState) -> (GameState, bool) {
             context,
             time_to_complete,
             best_time_to_complete,
+            stats,
+            leaderboard,
             resource_manager,
         } => {
             level_won_run(
                 context,
                 time_to_complete,
                 best_time_to_complete,
+                stats,
+                leaderboard,
                 resource_manager,
             )
             .await
         }
+        GameState::Leaderboard {
+            leaderboard,
+            previous,
+        } => leaderboard_run(leaderboard, previous).await,
     }
 }
@@ -9,6 +9,7 @@ use std::cmp::PartialEq;
 
 use crate::constants::KEYS_TURN_SPEED;
 use crate::constants::MOUSE_TURN_SPEED;
+use crate::constants::SPRINT_KEY;
 
 #[derive(Debug, Clone, Copy)]
 pub enum Operation {
@@ -19,6 +20,7 @@ pub enum Operation {
     StrafeLeft,
     StrafeRight,
     Shoot,
+    Sprint,
 }
 impl Hash for Operation {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -69,6 +71,7 @@ pub fn get_input() -> Vec<Operation> {
             KeyCode::A => Some(Operation::StrafeLeft),
             KeyCode::D => Some(Operation::StrafeRight),
             KeyCode::Space => Some(Operation::Shoot),
+            SPRINT_KEY => Some(Operation::Sprint),
             _ => None,
         })
         .chain(get_mouse_input())
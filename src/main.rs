@@ -1,6 +1,8 @@
+use console::Console;
 use focus_manager::FocusManager;
 use game_state::{run, GameState};
 
+mod console;
 mod constants;
 mod controller;
 mod draw;
@@ -11,17 +13,19 @@ mod input;
 mod math;
 mod model;
 mod renderer;
+mod scripting;
 mod service;
 
 #[macroquad::main("Game")]
 async fn main() {
     let mut game_state = GameState::initialise().await;
     let mut focus_manager = FocusManager::new();
+    let mut console = Console::default();
 
     loop {
         focus_manager.update();
         let should_exit;
-        (game_state, should_exit) = run(game_state).await;
+        (game_state, should_exit) = run(game_state, &mut console).await;
 
         if should_exit {
             break;
@@ -53,13 +53,56 @@ pub fn check_circles_collide(center1: Vec2, radius1: f32, center2: Vec2, radius2
     center1.distance(center2) <= radius1 + radius2
 }
 
+pub fn is_point_in_polygon(point: Vec2, polygon: &[Vec2]) -> bool {
+    use geo::Contains;
+
+    let polygon = geo::Polygon::new(
+        polygon
+            .iter()
+            .map(|vertex| geo::coord! { x: vertex.x, y: vertex.y })
+            .collect(),
+        vec![],
+    );
+    polygon.contains(&geo::coord! { x: point.x, y: point.y })
+}
+
+/// Cuts a line down to the part inside the box, None if it lies completely outside
+pub fn clip_line_to_rect(start: Vec2, end: Vec2, min: Vec2, max: Vec2) -> Option<(Vec2, Vec2)> {
+    let d = end - start;
+    let (mut t_start, mut t_end) = (0.0_f32, 1.0_f32);
+
+    // Liang-Barsky: each side limits the part of the line that can be inside
+    for (p, q) in [
+        (-d.x, start.x - min.x),
+        (d.x, max.x - start.x),
+        (-d.y, start.y - min.y),
+        (d.y, max.y - start.y),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+            continue;
+        }
+
+        let t = q / p;
+        if p < 0.0 {
+            t_start = t_start.max(t);
+        } else {
+            t_end = t_end.min(t);
+        }
+    }
+
+    (t_start <= t_end).then(|| (start + d * t_start, start + d * t_end))
+}
+
 pub fn find_perpendicular_vector(v: Vec2) -> Vec2 {
     vec2(-v.y, v.x)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::f32::{consts::PI, EPSILON};
+    use std::f32::consts::PI;
 
     use super::*;
 
@@ -85,8 +128,8 @@ mod tests {
 
         let result = rotate_point(point, origin, thetha);
 
-        assert!((result.x).abs() < EPSILON);
-        assert!((result.y - (-1.0)).abs() < EPSILON);
+        assert!((result.x).abs() < f32::EPSILON);
+        assert!((result.y - (-1.0)).abs() < f32::EPSILON);
     }
 
     #[test]
@@ -118,4 +161,46 @@ mod tests {
 
         assert!(!check_circles_collide(c1, r1, c3, r3));
     }
+
+    #[test]
+    fn test_is_point_in_polygon() {
+        let polygon = [
+            vec2(0.0, 0.0),
+            vec2(4.0, 0.0),
+            vec2(4.0, 4.0),
+            vec2(2.0, 2.0),
+            vec2(0.0, 4.0),
+        ];
+
+        assert!(is_point_in_polygon(vec2(1.0, 1.0), &polygon));
+        assert!(!is_point_in_polygon(vec2(2.0, 3.0), &polygon));
+        assert!(!is_point_in_polygon(vec2(5.0, 1.0), &polygon));
+    }
+
+    #[test]
+    fn test_clip_line_to_rect() {
+        let (min, max) = (vec2(0.0, 0.0), vec2(10.0, 10.0));
+
+        assert_eq!(
+            clip_line_to_rect(vec2(-5.0, 5.0), vec2(15.0, 5.0), min, max),
+            Some((vec2(0.0, 5.0), vec2(10.0, 5.0)))
+        );
+        assert_eq!(
+            clip_line_to_rect(vec2(2.0, 2.0), vec2(4.0, 6.0), min, max),
+            Some((vec2(2.0, 2.0), vec2(4.0, 6.0)))
+        );
+        assert_eq!(
+            clip_line_to_rect(vec2(-5.0, 12.0), vec2(15.0, 12.0), min, max),
+            None
+        );
+        assert_eq!(
+            clip_line_to_rect(
+                vec2(-1.0, 8.0),
+                vec2(8.0, 12.0),
+                vec2(0.0, 0.0),
+                vec2(2.0, 2.0)
+            ),
+            None
+        );
+    }
 }
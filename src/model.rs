@@ -1,21 +1,51 @@
 use std::{fmt::Debug, time::Duration};
 
+use automap::Automap;
+use checkpoint::Checkpoint;
 use decoration::Decoration;
-use enemy::Enemy;
+use difficulty::Difficulty;
+use enemy::{Enemy, EnemyType};
+use fog::Fog;
+use hazard::{Hazard, HazardSuit};
 use key_object::KeyObject;
+use level_stats::LevelStats;
+use light::Light;
 use macroquad::math::Vec2;
+use objective::Objective;
 use projectile::Projectile;
-use serde::Deserialize;
+use secret::Secret;
+use serde::{Deserialize, Serialize};
+use spawner::Spawner;
+use survival::SurvivalState;
+use teleporter::Teleporter;
+use trigger::Trigger;
 
-use crate::constants::{MAX_BULLETS, PLAYER_MAX_HEALTH};
+use crate::constants::{MAX_BULLETS, PLAYER_MAX_HEALTH, PLAYER_MAX_STAMINA};
 
+pub mod automap;
+pub mod checkpoint;
 pub mod decoration;
+pub mod difficulty;
 pub mod enemy;
+pub mod fog;
+pub mod game_mode;
+pub mod hazard;
 pub mod key_object;
+pub mod leaderboard;
+pub mod level_stats;
+pub mod light;
+pub mod objective;
 pub mod projectile;
+pub mod secret;
+pub mod spawner;
+pub mod speedrun;
+pub mod survival;
+pub mod teleporter;
+pub mod trigger;
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Deserialize, Serialize)]
 pub enum TextureId {
+    #[default]
     Debug,
     Stone,
     Metal,
@@ -56,6 +86,23 @@ pub enum TextureId {
     MeleeSlowEnemy8,
     Projectile,
     Skull,
+    Grate,
+    Window,
+    EnemyDeath1,
+    EnemyDeath2,
+    EnemyDeath3,
+    EnemyDeath4,
+    EnemyCorpse,
+    RangedEnemyDeath1,
+    RangedEnemyDeath2,
+    RangedEnemyDeath3,
+    RangedEnemyDeath4,
+    RangedEnemyCorpse,
+    MeleeSlowEnemyDeath1,
+    MeleeSlowEnemyDeath2,
+    MeleeSlowEnemyDeath3,
+    MeleeSlowEnemyDeath4,
+    MeleeSlowEnemyCorpse,
     Explostion1,
     Explostion2,
     Explostion3,
@@ -67,17 +114,14 @@ pub enum TextureId {
     Explostion9,
     TextFindTheKeys,
 }
-impl Default for TextureId {
-    fn default() -> Self {
-        Self::Debug
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Animation {
     Enemy,
     RangedEnemy,
     MeleeSlowEnemy,
+    EnemyDeath,
+    RangedEnemyDeath,
+    MeleeSlowEnemyDeath,
     Key,
     Gun,
     Explosion,
@@ -95,6 +139,24 @@ impl Animation {
                 TextureId::Enemy7,
                 TextureId::Enemy8,
             ],
+            Animation::EnemyDeath => vec![
+                TextureId::EnemyDeath1,
+                TextureId::EnemyDeath2,
+                TextureId::EnemyDeath3,
+                TextureId::EnemyDeath4,
+            ],
+            Animation::RangedEnemyDeath => vec![
+                TextureId::RangedEnemyDeath1,
+                TextureId::RangedEnemyDeath2,
+                TextureId::RangedEnemyDeath3,
+                TextureId::RangedEnemyDeath4,
+            ],
+            Animation::MeleeSlowEnemyDeath => vec![
+                TextureId::MeleeSlowEnemyDeath1,
+                TextureId::MeleeSlowEnemyDeath2,
+                TextureId::MeleeSlowEnemyDeath3,
+                TextureId::MeleeSlowEnemyDeath4,
+            ],
             Animation::Key => vec![TextureId::Key1, TextureId::Key2],
             Animation::Gun => vec![
                 TextureId::Gun1,
@@ -140,7 +202,7 @@ impl Animation {
     }
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Deserialize, Serialize)]
 pub enum SoundId {
     PickUpKey,
     PlayerTakeDamage,
@@ -150,30 +212,55 @@ pub enum SoundId {
     Escape,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 pub struct Entity {
     pub position: Vec2,
     pub size: f32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Player {
     pub entity: Entity,
     pub look: Vec2,
 }
+#[cfg(test)]
+impl Player {
+    /// Player of the usual size looking along +y, shared by the service tests
+    pub fn at(position: Vec2) -> Self {
+        Self {
+            entity: Entity {
+                position,
+                size: crate::constants::PLAYER_SIZE,
+            },
+            look: Vec2::Y,
+        }
+    }
+}
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Wall {
     pub texture: TextureId,
     pub start: Vec2,
     pub end: Vec2,
+    /// See-through wall, rays keep going past it and it's drawn with its texture's alpha
+    #[serde(default)]
+    pub is_masked: bool,
+    /// Hitscan shots and projectiles fly through it
+    #[serde(default)]
+    pub shots_pass_through: bool,
+    /// Name of the door the wall belongs to
+    #[serde(default)]
+    pub door: Option<String>,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum GameEvent {
     PickUpKey,
+    PickUpHazardSuit,
     EnemyKilled {
         position: Vec2,
+        enemy_type: EnemyType,
+        name: Option<String>,
     },
     LocationShot {
         position: Vec2,
@@ -184,22 +271,73 @@ pub enum GameEvent {
         direction: Vec2,
         damage: f32,
     },
+    SpawnEnemy {
+        position: Vec2,
+        enemy_type: EnemyType,
+    },
+    OpenDoor(String),
+    CloseDoor(String),
+    ShowMessage {
+        text: String,
+        duration: f32,
+    },
+    PlaySound(SoundId),
+    ChangeLighting(f32),
+    ChangeFog(Fog),
+    TeleportPlayer {
+        position: Vec2,
+        /// Keeps the current look direction if None
+        look: Option<Vec2>,
+    },
+    EndLevel,
+    TriggerFired(String),
+    ShotFired {
+        hit: bool,
+    },
+    SecretFound,
+    CheckpointReached,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Text shown in the middle of the screen for a limited time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Message {
+    pub text: String,
+    pub time_left: f32,
+}
+
+/// Walls that can be opened and closed by triggers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Door {
+    pub name: String,
+    pub walls: Vec<Wall>,
+    pub is_open: bool,
+    /// Closes once nothing stands in the doorway
+    #[serde(default)]
+    pub is_closing: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ShootingStatus {
     Shooting,
     NotShooting,
     Reloading,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PlayerInfo {
     pub shooting_status: ShootingStatus,
     pub time_since_last_shot: f32,
     pub bullets: usize,
+    pub max_bullets: usize,
     pub picked_up_keys: usize,
     pub health: f32,
+    pub stamina: f32,
+    pub is_exhausted: bool,
+    pub movement_speed: f32,
+    /// Seconds of hazard immunity left
+    pub hazard_suit_time: f32,
+    /// Seconds until the hazard the player stands in hurts again
+    pub hazard_tick_time: f32,
 }
 impl Default for PlayerInfo {
     fn default() -> Self {
@@ -209,11 +347,27 @@ impl Default for PlayerInfo {
             health: PLAYER_MAX_HEALTH,
             time_since_last_shot: 0.0,
             bullets: MAX_BULLETS,
+            max_bullets: MAX_BULLETS,
+            stamina: PLAYER_MAX_STAMINA,
+            is_exhausted: false,
+            movement_speed: 0.0,
+            hazard_suit_time: 0.0,
+            hazard_tick_time: 0.0,
+        }
+    }
+}
+impl PlayerInfo {
+    pub fn for_difficulty(difficulty: Difficulty) -> Self {
+        let max_bullets = difficulty.get_max_bullets();
+        Self {
+            bullets: max_bullets,
+            max_bullets,
+            ..Default::default()
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct GameObjects {
     pub player: Player,
     pub player_info: PlayerInfo,
@@ -223,4 +377,35 @@ pub struct GameObjects {
     pub exit_triggers: Vec<Entity>,
     pub decorations: Vec<Decoration>,
     pub projectiles: Vec<Projectile>,
+    pub spawners: Vec<Spawner>,
+    pub difficulty: Difficulty,
+    /// Set when playing in survival mode
+    pub survival: Option<SurvivalState>,
+    pub objectives: Vec<Objective>,
+    /// Seconds since the level started
+    pub level_time: f32,
+    pub triggers: Vec<Trigger>,
+    pub doors: Vec<Door>,
+    pub teleporters: Vec<Teleporter>,
+    pub hazards: Vec<Hazard>,
+    pub hazard_suits: Vec<HazardSuit>,
+    pub secrets: Vec<Secret>,
+    pub checkpoints: Vec<Checkpoint>,
+    pub stats: LevelStats,
+    /// Seconds left of the screen flash after teleporting
+    pub teleport_flash: f32,
+    pub message: Option<Message>,
+    pub light_level: f32,
+    /// Set by a trigger that ends the level regardless of the objectives
+    pub level_ended: bool,
+    /// Missing from saves made before the automap existed
+    #[serde(default)]
+    pub automap: Automap,
+    #[serde(default)]
+    pub lights: Vec<Light>,
+    #[serde(default)]
+    pub fog: Fog,
+    /// Names of the named enemies killed so far, for `KillTarget` objectives
+    #[serde(default)]
+    pub killed_targets: Vec<String>,
 }
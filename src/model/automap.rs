@@ -0,0 +1,19 @@
+use macroquad::math::Vec2;
+use serde::{Deserialize, Serialize};
+
+use super::Entity;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AutomapKey {
+    pub position: Vec2,
+    pub is_collected: bool,
+}
+
+/// The parts of the level the player has seen
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Automap {
+    /// Kept as segments rather than indices because opening doors changes the wall list
+    pub walls: Vec<(Vec2, Vec2)>,
+    pub keys: Vec<AutomapKey>,
+    pub exits: Vec<Entity>,
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use macroquad::{color::Color, math::Vec2};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::{CHECKPOINT_HEIGHT_OFFSET, CHECKPOINT_REACHED_TINT, CHECKPOINT_TINT},
+    draw::sprite_2d::Sprite2D,
+};
+
+use super::{Entity, TextureId};
+
+/// Saves the level state when touched, dying restarts from the last one reached
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub entity: Entity,
+    pub is_reached: bool,
+}
+impl Sprite2D for Checkpoint {
+    fn get_position(&self) -> Vec2 {
+        self.entity.position
+    }
+
+    fn get_vertical_offset(&self, _time_ellapsed: &Duration) -> f32 {
+        CHECKPOINT_HEIGHT_OFFSET
+    }
+
+    fn get_size(&self) -> f32 {
+        self.entity.size
+    }
+
+    fn get_texture(&self, _time_ellapsed: &Duration) -> TextureId {
+        TextureId::Projectile
+    }
+
+    fn get_tint(&self) -> Color {
+        if self.is_reached {
+            CHECKPOINT_REACHED_TINT
+        } else {
+            CHECKPOINT_TINT
+        }
+    }
+}
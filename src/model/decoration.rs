@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use macroquad::math::Vec2;
+use serde::{Deserialize, Serialize};
 
 use super::{Animation, Entity};
 use crate::{
@@ -8,21 +9,28 @@ use crate::{
     model::TextureId,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DecorationGraphics {
     Animation {
         animation: Animation,
         animation_speed: u128,
+        /// Repeats the animation forever, otherwise plays it once from the
+        /// decoration's creation and holds the last frame
+        looped: bool,
     },
     Texture(TextureId),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Decoration {
     pub entity: Entity,
     pub graphics: DecorationGraphics,
     pub life: Option<f32>,
     pub offset: f32,
+    /// Time since the decoration was created
+    pub age: f32,
+    /// Decoration that replaces this one once its life runs out
+    pub on_expire: Option<Box<Decoration>>,
 }
 impl Sprite2D for Decoration {
     fn get_position(&self) -> Vec2 {
@@ -42,12 +50,17 @@ impl Sprite2D for Decoration {
             DecorationGraphics::Animation {
                 animation,
                 animation_speed,
+                looped,
             } => {
                 let textures = animation.get_textures();
 
                 match textures.len() {
                     0 => TextureId::default(),
                     1 => textures[0],
+                    _ if !looped => {
+                        let frame = (self.age * 1000.0) as u128 / animation_speed;
+                        textures[(frame as usize).min(textures.len() - 1)]
+                    }
                     _ => select_animation_texture(&textures, animation_speed, time_ellapsed),
                 }
             }
@@ -57,17 +70,19 @@ impl Sprite2D for Decoration {
 }
 impl Decoration {
     pub fn update(self, delta: f32) -> Option<Self> {
-        if self.life.is_none() {
-            return Some(self);
-        }
+        let age = self.age + delta;
+        let Some(life) = self.life else {
+            return Some(Self { age, ..self });
+        };
 
-        let new_life = self.life.unwrap() - delta;
+        let new_life = life - delta;
         if new_life <= 0.0 {
-            return None;
+            return self.on_expire.map(|next| *next);
         }
 
         Some(Self {
             life: Some(new_life),
+            age,
             ..self
         })
     }
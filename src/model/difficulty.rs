@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    EASY_AMMO_MODIFIER, EASY_ENEMY_DAMAGE_MODIFIER, EASY_ENEMY_HEALTH_MODIFIER,
+    EASY_ENEMY_SPEED_MODIFIER, EASY_PLAYER_REGENERATION_MODIFIER, HARD_AMMO_MODIFIER,
+    HARD_ENEMY_DAMAGE_MODIFIER, HARD_ENEMY_HEALTH_MODIFIER, HARD_ENEMY_SPEED_MODIFIER,
+    HARD_PLAYER_REGENERATION_MODIFIER, MAX_BULLETS,
+};
+
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn next(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Hard,
+            Difficulty::Normal => Difficulty::Easy,
+            Difficulty::Hard => Difficulty::Normal,
+        }
+    }
+
+    pub fn get_enemy_health_modifier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => EASY_ENEMY_HEALTH_MODIFIER,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => HARD_ENEMY_HEALTH_MODIFIER,
+        }
+    }
+
+    pub fn get_enemy_damage_modifier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => EASY_ENEMY_DAMAGE_MODIFIER,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => HARD_ENEMY_DAMAGE_MODIFIER,
+        }
+    }
+
+    pub fn get_enemy_speed_modifier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => EASY_ENEMY_SPEED_MODIFIER,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => HARD_ENEMY_SPEED_MODIFIER,
+        }
+    }
+
+    pub fn get_player_regeneration_modifier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => EASY_PLAYER_REGENERATION_MODIFIER,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => HARD_PLAYER_REGENERATION_MODIFIER,
+        }
+    }
+
+    pub fn get_max_bullets(&self) -> usize {
+        let modifier = match self {
+            Difficulty::Easy => EASY_AMMO_MODIFIER,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => HARD_AMMO_MODIFIER,
+        };
+
+        (MAX_BULLETS as f32 * modifier).round() as usize
+    }
+}
@@ -2,18 +2,32 @@ use std::time::Duration;
 
 use crate::{
     constants::{
+        BOSS_ANIMATION_SPEED, BOSS_ATTACK_RANGE, BOSS_CHARGE_PHASE_HEALTH, BOSS_HEALTH,
+        BOSS_KNOCKBACK_DISTANCE, BOSS_MOVE_SPEED, BOSS_SCORE, BOSS_SIZE, BOSS_SPREAD_ATTACK_DELAY,
+        BOSS_SPREAD_DAMAGE, BOSS_STAGGER_TIME, BOSS_SUMMON_PHASE_HEALTH, BOSS_TINT,
         ENEMY_BASE_HEIGHT_OFFSET, ENEMY_HEIGHT_AMPLITUDE, ENEMY_HEIGHT_CHANGE_ANIMATION_SPEED,
-        MELEE_ENEMY_ATTACK_DELAY, MELEE_ENEMY_ATTACK_RANGE, MELEE_ENEMY_DAMAGE,
-        MELEE_ENEMY_MOVE_SPEED, MELEE_SLOW_ENEMY_ANIMATION_SPEED, MELEE_SLOW_ENEMY_ATTACK_DELAY,
+        ENEMY_HIT_FLASH_COLOR, ENEMY_KNOCKBACK_DISTANCE, ENEMY_STAGGER_TIME,
+        KAMIKAZE_ENEMY_ANIMATION_SPEED, KAMIKAZE_ENEMY_ATTACK_RANGE, KAMIKAZE_ENEMY_DAMAGE,
+        KAMIKAZE_ENEMY_HEALTH, KAMIKAZE_ENEMY_MOVE_SPEED, KAMIKAZE_ENEMY_SCORE,
+        KAMIKAZE_ENEMY_TINT, KAMIKAZE_EXPLOSION_RADIUS, MELEE_ENEMY_ATTACK_DELAY,
+        MELEE_ENEMY_ATTACK_RANGE, MELEE_ENEMY_DAMAGE, MELEE_ENEMY_MOVE_SPEED, MELEE_ENEMY_SCORE,
+        MELEE_SLOW_ENEMY_ANIMATION_SPEED, MELEE_SLOW_ENEMY_ATTACK_DELAY,
         MELEE_SLOW_ENEMY_ATTACK_RANGE, MELEE_SLOW_ENEMY_DAMAGE, MELEE_SLOW_ENEMY_HEALTH,
-        MELEE_SLOW_ENEMY_MOVE_SPEED, RANGED_ENEMY_ANIMATION_SPEED, RANGED_ENEMY_ATTACK_DELAY,
-        RANGED_ENEMY_DAMAGE, RANGED_ENEMY_MOVE_SPEED, RANGED_ENEMY_SHOOT_RANGE,
+        MELEE_SLOW_ENEMY_KNOCKBACK_DISTANCE, MELEE_SLOW_ENEMY_MOVE_SPEED, MELEE_SLOW_ENEMY_SCORE,
+        MELEE_SLOW_ENEMY_STAGGER_TIME, RANGED_ENEMY_ANIMATION_SPEED, RANGED_ENEMY_ATTACK_DELAY,
+        RANGED_ENEMY_DAMAGE, RANGED_ENEMY_MOVE_SPEED, RANGED_ENEMY_SCORE, RANGED_ENEMY_SHOOT_RANGE,
+        SUMMONER_ENEMY_ANIMATION_SPEED, SUMMONER_ENEMY_HEALTH, SUMMONER_ENEMY_MOVE_SPEED,
+        SUMMONER_ENEMY_SCORE, SUMMONER_ENEMY_SUMMON_DELAY, SUMMONER_ENEMY_SUMMON_RANGE,
+        SUMMONER_ENEMY_TINT,
     },
-    model::Animation,
+    model::{difficulty::Difficulty, Animation},
     service::id_generator::generate_id,
 };
-use macroquad::math::{vec2, Vec2};
-use serde::Deserialize;
+use macroquad::{
+    color::{Color, WHITE},
+    math::{vec2, Vec2},
+};
+use serde::{Deserialize, Serialize};
 
 use crate::{
     constants::{ENEMY_HP, ENEMY_SIZE, MELEE_ENEMY_ANIMATION_SPEED},
@@ -22,42 +36,64 @@ use crate::{
 
 use super::{Entity, TextureId};
 
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 pub enum EnemyType {
     Melee,
     MeleeSlow,
     Ranged,
+    Boss,
+    Kamikaze,
+    Summoner,
 }
 impl EnemyType {
-    pub fn to_enemy(self, position: Vec2) -> Enemy {
-        let id = generate_id();
-        let entity = Entity {
-            position,
-            size: ENEMY_SIZE,
-        };
-
-        match self {
-            EnemyType::Melee => Enemy {
-                id,
-                entity,
-                hp: ENEMY_HP,
-                attack_delay: 0.0,
-                enemy_type: self,
-            },
-            EnemyType::Ranged => Enemy {
-                id,
-                entity,
-                hp: ENEMY_HP,
-                attack_delay: RANGED_ENEMY_ATTACK_DELAY,
-                enemy_type: self,
-            },
-            EnemyType::MeleeSlow => Enemy {
-                id,
-                entity,
-                hp: MELEE_SLOW_ENEMY_HEALTH,
-                attack_delay: 0.0,
-                enemy_type: self,
+    pub fn to_enemy(self, position: Vec2, difficulty: Difficulty) -> Enemy {
+        let hp = self.get_health() * difficulty.get_enemy_health_modifier();
+        Enemy {
+            id: generate_id(),
+            entity: Entity {
+                position,
+                size: self.get_size(),
             },
+            hp,
+            max_hp: hp,
+            attack_delay: self.get_initial_attack_delay(),
+            enemy_type: self,
+            difficulty,
+            hit_timer: 0.0,
+            stagger_timer: 0.0,
+            is_engaged: false,
+            charge: None,
+            name: None,
+        }
+    }
+
+    pub fn get_size(&self) -> f32 {
+        match self {
+            EnemyType::Melee
+            | EnemyType::MeleeSlow
+            | EnemyType::Ranged
+            | EnemyType::Kamikaze
+            | EnemyType::Summoner => ENEMY_SIZE,
+            EnemyType::Boss => BOSS_SIZE,
+        }
+    }
+
+    pub fn get_health(&self) -> f32 {
+        match self {
+            EnemyType::Melee | EnemyType::Ranged => ENEMY_HP,
+            EnemyType::MeleeSlow => MELEE_SLOW_ENEMY_HEALTH,
+            EnemyType::Boss => BOSS_HEALTH,
+            EnemyType::Kamikaze => KAMIKAZE_ENEMY_HEALTH,
+            EnemyType::Summoner => SUMMONER_ENEMY_HEALTH,
+        }
+    }
+
+    pub fn get_initial_attack_delay(&self) -> f32 {
+        match self {
+            EnemyType::Melee | EnemyType::MeleeSlow | EnemyType::Kamikaze => 0.0,
+            EnemyType::Ranged => RANGED_ENEMY_ATTACK_DELAY,
+            EnemyType::Boss => BOSS_SPREAD_ATTACK_DELAY,
+            EnemyType::Summoner => SUMMONER_ENEMY_SUMMON_DELAY,
         }
     }
 
@@ -66,6 +102,9 @@ impl EnemyType {
             EnemyType::Melee => MELEE_ENEMY_MOVE_SPEED,
             EnemyType::Ranged => RANGED_ENEMY_MOVE_SPEED,
             EnemyType::MeleeSlow => MELEE_SLOW_ENEMY_MOVE_SPEED,
+            EnemyType::Boss => BOSS_MOVE_SPEED,
+            EnemyType::Kamikaze => KAMIKAZE_ENEMY_MOVE_SPEED,
+            EnemyType::Summoner => SUMMONER_ENEMY_MOVE_SPEED,
         }
     }
 
@@ -74,6 +113,9 @@ impl EnemyType {
             EnemyType::Melee => MELEE_ENEMY_ATTACK_RANGE,
             EnemyType::Ranged => RANGED_ENEMY_SHOOT_RANGE,
             EnemyType::MeleeSlow => MELEE_SLOW_ENEMY_ATTACK_RANGE,
+            EnemyType::Boss => BOSS_ATTACK_RANGE,
+            EnemyType::Kamikaze => KAMIKAZE_ENEMY_ATTACK_RANGE,
+            EnemyType::Summoner => SUMMONER_ENEMY_SUMMON_RANGE,
         }
     }
 
@@ -82,22 +124,63 @@ impl EnemyType {
             EnemyType::Melee => MELEE_ENEMY_DAMAGE,
             EnemyType::MeleeSlow => MELEE_SLOW_ENEMY_DAMAGE,
             EnemyType::Ranged => RANGED_ENEMY_DAMAGE,
+            EnemyType::Boss => BOSS_SPREAD_DAMAGE,
+            EnemyType::Kamikaze => KAMIKAZE_ENEMY_DAMAGE,
+            EnemyType::Summoner => 0.0,
         }
     }
 
     pub fn get_attack_speed(&self) -> f32 {
         match self {
-            EnemyType::Melee => MELEE_ENEMY_ATTACK_DELAY,
+            EnemyType::Melee | EnemyType::Kamikaze => MELEE_ENEMY_ATTACK_DELAY,
             EnemyType::Ranged => RANGED_ENEMY_ATTACK_DELAY,
             EnemyType::MeleeSlow => MELEE_SLOW_ENEMY_ATTACK_DELAY,
+            EnemyType::Boss => BOSS_SPREAD_ATTACK_DELAY,
+            EnemyType::Summoner => SUMMONER_ENEMY_SUMMON_DELAY,
         }
     }
 
     pub fn get_animation(&self) -> Vec<TextureId> {
         match self {
-            EnemyType::Melee => Animation::Enemy.get_textures(),
-            EnemyType::Ranged => Animation::RangedEnemy.get_textures(),
-            EnemyType::MeleeSlow => Animation::MeleeSlowEnemy.get_textures(),
+            EnemyType::Melee | EnemyType::Kamikaze => Animation::Enemy.get_textures(),
+            EnemyType::Ranged | EnemyType::Summoner => Animation::RangedEnemy.get_textures(),
+            EnemyType::MeleeSlow | EnemyType::Boss => Animation::MeleeSlowEnemy.get_textures(),
+        }
+    }
+
+    pub fn get_death_animation(&self) -> Animation {
+        match self {
+            EnemyType::Melee | EnemyType::Kamikaze => Animation::EnemyDeath,
+            EnemyType::Ranged | EnemyType::Summoner => Animation::RangedEnemyDeath,
+            EnemyType::MeleeSlow | EnemyType::Boss => Animation::MeleeSlowEnemyDeath,
+        }
+    }
+
+    pub fn get_corpse_texture(&self) -> TextureId {
+        match self {
+            EnemyType::Melee | EnemyType::Kamikaze => TextureId::EnemyCorpse,
+            EnemyType::Ranged | EnemyType::Summoner => TextureId::RangedEnemyCorpse,
+            EnemyType::MeleeSlow | EnemyType::Boss => TextureId::MeleeSlowEnemyCorpse,
+        }
+    }
+
+    pub fn get_stagger_time(&self) -> f32 {
+        match self {
+            EnemyType::Melee | EnemyType::Ranged | EnemyType::Kamikaze | EnemyType::Summoner => {
+                ENEMY_STAGGER_TIME
+            }
+            EnemyType::MeleeSlow => MELEE_SLOW_ENEMY_STAGGER_TIME,
+            EnemyType::Boss => BOSS_STAGGER_TIME,
+        }
+    }
+
+    pub fn get_knockback_distance(&self) -> f32 {
+        match self {
+            EnemyType::Melee | EnemyType::Ranged | EnemyType::Kamikaze | EnemyType::Summoner => {
+                ENEMY_KNOCKBACK_DISTANCE
+            }
+            EnemyType::MeleeSlow => MELEE_SLOW_ENEMY_KNOCKBACK_DISTANCE,
+            EnemyType::Boss => BOSS_KNOCKBACK_DISTANCE,
         }
     }
 
@@ -106,17 +189,117 @@ impl EnemyType {
             EnemyType::Melee => MELEE_ENEMY_ANIMATION_SPEED,
             EnemyType::Ranged => RANGED_ENEMY_ANIMATION_SPEED,
             EnemyType::MeleeSlow => MELEE_SLOW_ENEMY_ANIMATION_SPEED,
+            EnemyType::Boss => BOSS_ANIMATION_SPEED,
+            EnemyType::Kamikaze => KAMIKAZE_ENEMY_ANIMATION_SPEED,
+            EnemyType::Summoner => SUMMONER_ENEMY_ANIMATION_SPEED,
         }
     }
+
+    pub fn get_tint(&self) -> Color {
+        match self {
+            EnemyType::Melee | EnemyType::MeleeSlow | EnemyType::Ranged => WHITE,
+            EnemyType::Boss => BOSS_TINT,
+            EnemyType::Kamikaze => KAMIKAZE_ENEMY_TINT,
+            EnemyType::Summoner => SUMMONER_ENEMY_TINT,
+        }
+    }
+
+    pub fn get_score(&self) -> u32 {
+        match self {
+            EnemyType::Melee => MELEE_ENEMY_SCORE,
+            EnemyType::MeleeSlow => MELEE_SLOW_ENEMY_SCORE,
+            EnemyType::Ranged => RANGED_ENEMY_SCORE,
+            EnemyType::Kamikaze => KAMIKAZE_ENEMY_SCORE,
+            EnemyType::Summoner => SUMMONER_ENEMY_SCORE,
+            EnemyType::Boss => BOSS_SCORE,
+        }
+    }
+
+    /// Radius of the blast the enemy leaves behind when it dies, if it explodes
+    pub fn get_death_explosion_radius(&self) -> Option<f32> {
+        match self {
+            EnemyType::Melee
+            | EnemyType::MeleeSlow
+            | EnemyType::Ranged
+            | EnemyType::Boss
+            | EnemyType::Summoner => None,
+            EnemyType::Kamikaze => Some(KAMIKAZE_EXPLOSION_RADIUS),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BossPhase {
+    Spread,
+    Charge,
+    Summon,
+}
+impl BossPhase {
+    pub fn for_health_ratio(health_ratio: f32) -> Self {
+        if health_ratio > BOSS_CHARGE_PHASE_HEALTH {
+            BossPhase::Spread
+        } else if health_ratio > BOSS_SUMMON_PHASE_HEALTH {
+            BossPhase::Charge
+        } else {
+            BossPhase::Summon
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Charge {
+    pub direction: Vec2,
+    pub time_left: f32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Enemy {
     pub id: u64,
     pub entity: Entity,
     pub hp: f32,
+    pub max_hp: f32,
     pub attack_delay: f32,
     pub enemy_type: EnemyType,
+    pub difficulty: Difficulty,
+    /// Time left to draw the enemy tinted after being hit
+    pub hit_timer: f32,
+    /// Time left before a staggered enemy can move and attack again
+    pub stagger_timer: f32,
+    /// Set once the enemy has seen the player or been shot
+    pub is_engaged: bool,
+    pub charge: Option<Charge>,
+    /// Name used by level objectives to refer to this enemy
+    pub name: Option<String>,
+}
+impl Enemy {
+    pub fn get_movement_speed(&self) -> f32 {
+        self.enemy_type.get_movement_speed() * self.difficulty.get_enemy_speed_modifier()
+    }
+
+    pub fn get_attack_damage(&self) -> f32 {
+        self.enemy_type.get_attack_damage() * self.difficulty.get_enemy_damage_modifier()
+    }
+
+    /// Attacks with projectiles or hitscan, which fly through walls that shots pass through
+    pub fn shoots_through_fences(&self) -> bool {
+        match self.enemy_type {
+            EnemyType::Ranged => true,
+            EnemyType::Boss => {
+                BossPhase::for_health_ratio(self.get_health_ratio()) == BossPhase::Spread
+            }
+            EnemyType::Melee | EnemyType::MeleeSlow | EnemyType::Kamikaze | EnemyType::Summoner => {
+                false
+            }
+        }
+    }
+
+    pub fn is_staggered(&self) -> bool {
+        self.stagger_timer > 0.0
+    }
+
+    pub fn get_health_ratio(&self) -> f32 {
+        (self.hp / self.max_hp).clamp(0.0, 1.0)
+    }
 }
 impl Sprite2D for Enemy {
     fn get_position(&self) -> Vec2 {
@@ -127,7 +310,7 @@ impl Sprite2D for Enemy {
         calculate_vertical_offset(
             ENEMY_HEIGHT_CHANGE_ANIMATION_SPEED,
             ENEMY_HEIGHT_AMPLITUDE,
-            ENEMY_BASE_HEIGHT_OFFSET,
+            ENEMY_BASE_HEIGHT_OFFSET + ENEMY_SIZE - self.entity.size,
             ENEMY_HEIGHT_AMPLITUDE,
             time_ellapsed,
         )
@@ -144,9 +327,17 @@ impl Sprite2D for Enemy {
             time_ellapsed,
         )
     }
+
+    fn get_tint(&self) -> Color {
+        if self.hit_timer > 0.0 {
+            ENEMY_HIT_FLASH_COLOR
+        } else {
+            self.enemy_type.get_tint()
+        }
+    }
 }
 impl Default for Enemy {
     fn default() -> Self {
-        EnemyType::Melee.to_enemy(vec2(0.0, 0.0))
+        EnemyType::Melee.to_enemy(vec2(0.0, 0.0), Difficulty::default())
     }
 }
@@ -0,0 +1,83 @@
+use macroquad::{
+    color::Color,
+    math::{vec3, Vec3},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{DEFAULT_FOG_COLOR, FOG_MIN_VISIBILITY};
+
+/// Haze that blends everything toward its colour the further away it is
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Fog {
+    /// Red, green and blue in 0.0..=1.0
+    pub color: Vec3,
+    /// 0.0 is no fog, higher values hide things closer to the player
+    pub density: f32,
+}
+impl Default for Fog {
+    fn default() -> Self {
+        Self {
+            color: DEFAULT_FOG_COLOR,
+            density: 0.0,
+        }
+    }
+}
+impl Fog {
+    /// How much of something `distance` away is hidden, 0.0 clear to 1.0 hidden
+    pub fn get_amount(&self, distance: f32) -> f32 {
+        if self.density <= 0.0 {
+            return 0.0;
+        }
+
+        1.0 - (-self.density * distance).exp()
+    }
+
+    /// Distance from which nothing is worth drawing, None without fog
+    pub fn get_visible_distance(&self) -> Option<f32> {
+        (self.density > 0.0).then(|| -FOG_MIN_VISIBILITY.ln() / self.density)
+    }
+
+    /// Fog colour with the amount of fog at `distance` as its alpha
+    pub fn get_overlay(&self, distance: f32) -> Color {
+        Color::new(
+            self.color.x,
+            self.color.y,
+            self.color.z,
+            self.get_amount(distance),
+        )
+    }
+
+    pub fn apply(&self, color: Color, distance: f32) -> Color {
+        let amount = self.get_amount(distance);
+        let mixed = vec3(color.r, color.g, color.b).lerp(self.color, amount);
+        Color::new(mixed.x, mixed.y, mixed.z, color.a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fog() {
+        let fog = Fog {
+            color: vec3(1.0, 1.0, 1.0),
+            density: 0.5,
+        };
+
+        assert_eq!(fog.get_amount(0.0), 0.0);
+        assert!(fog.get_amount(2.0) < fog.get_amount(4.0));
+
+        let visible_distance = fog.get_visible_distance().unwrap();
+        assert!((fog.get_amount(visible_distance) - (1.0 - FOG_MIN_VISIBILITY)).abs() < 0.0001);
+
+        let black = Color::new(0.0, 0.0, 0.0, 1.0);
+        let fogged = fog.apply(black, visible_distance);
+        assert!((fogged.r - (1.0 - FOG_MIN_VISIBILITY)).abs() < 0.0001);
+        assert_eq!(fogged.a, 1.0);
+
+        let clear = Fog::default();
+        assert_eq!(clear.get_amount(100.0), 0.0);
+        assert_eq!(clear.get_visible_distance(), None);
+    }
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameMode {
+    /// Collect the keys and find the exit
+    #[default]
+    Campaign,
+    /// Survive escalating waves of enemies for as long as possible
+    Survival,
+    /// Every campaign level back to back against the clock
+    Speedrun,
+}
+impl GameMode {
+    pub fn next(self) -> Self {
+        match self {
+            GameMode::Campaign => GameMode::Survival,
+            GameMode::Survival => GameMode::Speedrun,
+            GameMode::Speedrun => GameMode::Campaign,
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use macroquad::{color::Color, math::Vec2};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::{
+        ACID_DAMAGE, ACID_TINT, HAZARD_SUIT_HEIGHT_OFFSET, HAZARD_SUIT_TINT, LAVA_DAMAGE,
+        LAVA_TINT, RADIATION_DAMAGE, RADIATION_TINT,
+    },
+    draw::sprite_2d::Sprite2D,
+};
+
+use super::{Entity, TextureId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum HazardType {
+    Lava,
+    Acid,
+    Radiation,
+}
+impl HazardType {
+    /// Damage dealt every `HAZARD_TICK_INTERVAL` seconds
+    pub fn get_damage(&self) -> f32 {
+        match self {
+            HazardType::Lava => LAVA_DAMAGE,
+            HazardType::Acid => ACID_DAMAGE,
+            HazardType::Radiation => RADIATION_DAMAGE,
+        }
+    }
+
+    pub fn get_tint(&self) -> Color {
+        match self {
+            HazardType::Lava => LAVA_TINT,
+            HazardType::Acid => ACID_TINT,
+            HazardType::Radiation => RADIATION_TINT,
+        }
+    }
+}
+
+/// Floor area that hurts the player while they stand in it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Hazard {
+    pub hazard_type: HazardType,
+    pub polygon: Vec<Vec2>,
+}
+
+/// Pickup that makes the player immune to hazards for a while
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HazardSuit {
+    pub entity: Entity,
+}
+impl Sprite2D for HazardSuit {
+    fn get_position(&self) -> Vec2 {
+        self.entity.position
+    }
+
+    fn get_vertical_offset(&self, _time_ellapsed: &Duration) -> f32 {
+        HAZARD_SUIT_HEIGHT_OFFSET
+    }
+
+    fn get_size(&self) -> f32 {
+        self.entity.size
+    }
+
+    fn get_texture(&self, _time_ellapsed: &Duration) -> TextureId {
+        TextureId::Skull
+    }
+
+    fn get_tint(&self) -> Color {
+        HAZARD_SUIT_TINT
+    }
+}
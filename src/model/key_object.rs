@@ -3,10 +3,11 @@ use crate::{
     draw::{calculate_vertical_offset, select_animation_texture, sprite_2d::Sprite2D},
 };
 use macroquad::math::vec2;
+use serde::{Deserialize, Serialize};
 
 use super::*;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct KeyObject {
     pub entity: Entity,
 }
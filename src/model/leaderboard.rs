@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::{difficulty::Difficulty, level_stats::LevelStats};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    /// Seconds since the Unix epoch
+    pub date: u64,
+    pub difficulty: Difficulty,
+    pub time: Duration,
+    pub stats: LevelStats,
+}
+
+/// Fastest full runs of a level on one difficulty, fastest first
+#[derive(Debug, Clone, Default)]
+pub struct Leaderboard {
+    pub level: u32,
+    pub difficulty: Difficulty,
+    pub entries: Vec<LeaderboardEntry>,
+    /// Position of the entry the last run added, highlighted when shown
+    pub new_record: Option<usize>,
+}
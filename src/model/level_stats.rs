@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// Statistics shown when the level is completed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LevelStats {
+    pub kills: usize,
+    /// Filled in when the level ends, includes enemies spawned during the level
+    pub total_enemies: usize,
+    pub secrets_found: usize,
+    pub total_secrets: usize,
+    pub shots_fired: usize,
+    pub shots_hit: usize,
+    pub damage_taken: f32,
+}
+impl LevelStats {
+    /// Percentage of shots that hit an enemy
+    pub fn get_accuracy(&self) -> f32 {
+        if self.shots_fired == 0 {
+            return 0.0;
+        }
+
+        self.shots_hit as f32 / self.shots_fired as f32 * 100.0
+    }
+}
@@ -0,0 +1,64 @@
+use macroquad::math::{Vec2, Vec3};
+use serde::{Deserialize, Serialize};
+
+/// Coloured point light, placed in the level or left behind by shots and explosions
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Light {
+    pub position: Vec2,
+    /// Red, green and blue in 0.0..=1.0
+    pub color: Vec3,
+    pub radius: f32,
+    pub intensity: f32,
+    /// Seconds left for temporary lights, None for lights that never go out
+    pub time_left: Option<f32>,
+    /// Seconds a temporary light takes to fade out completely
+    pub lifetime: f32,
+}
+impl Light {
+    pub fn new(position: Vec2, color: Vec3, radius: f32, intensity: f32) -> Self {
+        Self {
+            position,
+            color,
+            radius,
+            intensity,
+            time_left: None,
+            lifetime: 0.0,
+        }
+    }
+
+    pub fn temporary(
+        position: Vec2,
+        color: Vec3,
+        radius: f32,
+        intensity: f32,
+        lifetime: f32,
+    ) -> Self {
+        Self {
+            time_left: Some(lifetime),
+            lifetime,
+            ..Self::new(position, color, radius, intensity)
+        }
+    }
+
+    /// Intensity after fading, temporary lights dim linearly until they expire
+    pub fn get_current_intensity(&self) -> f32 {
+        match self.time_left {
+            Some(time_left) if self.lifetime > 0.0 => {
+                self.intensity * (time_left / self.lifetime).clamp(0.0, 1.0)
+            }
+            Some(_) => 0.0,
+            None => self.intensity,
+        }
+    }
+
+    pub fn update(self, delta: f32) -> Option<Self> {
+        match self.time_left {
+            Some(time_left) if time_left - delta <= 0.0 => None,
+            Some(time_left) => Some(Self {
+                time_left: Some(time_left - delta),
+                ..self
+            }),
+            None => Some(self),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use macroquad::math::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// A goal the player has to complete to finish a level
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Objective {
+    CollectAllKeys,
+    ReachExit,
+    KillAllEnemies,
+    /// Kill the enemy with the given name
+    KillTarget(String),
+    /// Stay alive for the given number of seconds
+    Survive(f32),
+    ReachLocation {
+        position: Vec2,
+        radius: f32,
+    },
+    /// Pick up at least the given number of keys, not necessarily all of them
+    #[serde(alias = "CollectItems")]
+    CollectKeys(usize),
+    AllOf(Vec<Objective>),
+    AnyOf(Vec<Objective>),
+}
+impl Objective {
+    /// Objectives of levels that don't declare any
+    pub fn default_objectives() -> Vec<Objective> {
+        vec![Objective::CollectAllKeys, Objective::ReachExit]
+    }
+
+    /// Whether this objective is `objective` or has it nested inside
+    pub fn includes(&self, objective: &Objective) -> bool {
+        match self {
+            Objective::AllOf(objectives) | Objective::AnyOf(objectives) => {
+                objectives.iter().any(|nested| nested.includes(objective))
+            }
+            _ => self == objective,
+        }
+    }
+}
@@ -1,12 +1,13 @@
 use std::time::Duration;
 
 use macroquad::math::Vec2;
+use serde::{Deserialize, Serialize};
 
 use crate::{constants::PROJECTILE_OFFSET, draw::sprite_2d::Sprite2D};
 
 use super::{Entity, TextureId};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Projectile {
     pub entity: Entity,
     pub direction: Vec2,
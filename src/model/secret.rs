@@ -0,0 +1,9 @@
+use macroquad::math::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// Hidden area that counts towards the level statistics once the player enters it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Secret {
+    pub polygon: Vec<Vec2>,
+    pub is_found: bool,
+}
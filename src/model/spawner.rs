@@ -0,0 +1,31 @@
+use macroquad::math::Vec2;
+use serde::{Deserialize, Serialize};
+
+use super::enemy::EnemyType;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub enum SpawnTrigger {
+    #[default]
+    Always,
+    /// Starts spawning once the player comes within the given distance
+    PlayerInRange(f32),
+    /// Starts spawning once the player has picked up the given number of keys
+    KeysPickedUp(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Spawner {
+    pub position: Vec2,
+    pub enemy_type: EnemyType,
+    pub trigger: SpawnTrigger,
+    /// Stays set once the trigger has fired
+    pub is_triggered: bool,
+    pub interval: f32,
+    pub time_until_spawn: f32,
+    /// Maximum number of this spawner's enemies alive at the same time
+    pub max_alive: usize,
+    /// Enemies left to spawn, unlimited if `None`
+    pub remaining: Option<u32>,
+    /// Ids of the enemies created by this spawner that may still be alive
+    pub spawned_ids: Vec<u64>,
+}
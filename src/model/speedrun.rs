@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+/// A timed run through every level in one go, compared against the personal best
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Speedrun {
+    /// Game time spent in each finished level
+    pub splits: Vec<Duration>,
+    /// Cumulative split times of the personal best run, empty without one
+    pub best_splits: Vec<Duration>,
+}
+impl Speedrun {
+    pub fn new(best_splits: Vec<Duration>) -> Self {
+        Self {
+            splits: vec![],
+            best_splits,
+        }
+    }
+
+    /// Time since the start of the run at the end of each finished level
+    pub fn get_cumulative_splits(&self) -> Vec<Duration> {
+        self.splits
+            .iter()
+            .scan(Duration::ZERO, |total, split| {
+                *total += *split;
+                Some(*total)
+            })
+            .collect()
+    }
+
+    pub fn get_total(&self, level_time: Duration) -> Duration {
+        self.splits.iter().sum::<Duration>() + level_time
+    }
+
+    /// Seconds ahead (negative) or behind the personal best at the end of a split
+    pub fn get_delta(&self, index: usize, cumulative: Duration) -> Option<f64> {
+        self.best_splits
+            .get(index)
+            .map(|best| cumulative.as_secs_f64() - best.as_secs_f64())
+    }
+
+    pub fn is_personal_best(&self) -> bool {
+        // A best run over a different set of levels can't be compared
+        match self.best_splits.last() {
+            Some(best) if self.best_splits.len() == self.splits.len() => {
+                self.get_total(Duration::ZERO) < *best
+            }
+            _ => true,
+        }
+    }
+}
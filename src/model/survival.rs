@@ -0,0 +1,35 @@
+use macroquad::math::Vec2;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{SURVIVAL_FIRST_WAVE_DELAY, SURVIVAL_SCORE_PER_SECOND};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SurvivalState {
+    /// Last wave that was spawned, 0 before the first wave
+    pub wave: u32,
+    pub time_until_next_wave: f32,
+    pub time_survived: f32,
+    pub kill_score: u32,
+    pub spawn_points: Vec<Vec2>,
+}
+impl SurvivalState {
+    pub fn new(spawn_points: Vec<Vec2>) -> Self {
+        Self {
+            time_until_next_wave: SURVIVAL_FIRST_WAVE_DELAY,
+            spawn_points,
+            ..Default::default()
+        }
+    }
+
+    pub fn get_score(&self) -> u32 {
+        self.kill_score + self.time_survived as u32 * SURVIVAL_SCORE_PER_SECOND
+    }
+}
+
+/// Outcome of a finished survival run, shown on the game over screen
+#[derive(Debug, Clone)]
+pub struct SurvivalResult {
+    pub score: u32,
+    pub wave: u32,
+    pub high_scores: Vec<u32>,
+}
@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use macroquad::{color::Color, math::Vec2};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::{
+        TELEPORTER_COOLDOWN_TINT, TELEPORTER_DRAW_SIZE_MOD, TELEPORTER_HEIGHT_OFFSET,
+        TELEPORTER_TINT,
+    },
+    draw::sprite_2d::Sprite2D,
+};
+
+use super::{Entity, TextureId};
+
+/// One pad of a teleporter pair, sends whatever steps on it to the other pad
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Teleporter {
+    pub entity: Entity,
+    pub destination: Vec2,
+    /// Player look direction after teleporting, the current one is kept if None
+    pub destination_look: Option<Vec2>,
+    pub teleports_enemies: bool,
+    pub teleports_projectiles: bool,
+    /// Seconds until the pad can be used again
+    pub cooldown: f32,
+    /// The player has to step off the pad before it can send them back
+    pub is_player_inside: bool,
+}
+impl Teleporter {
+    pub fn is_ready(&self) -> bool {
+        self.cooldown <= 0.0
+    }
+}
+impl Sprite2D for Teleporter {
+    fn get_position(&self) -> Vec2 {
+        self.entity.position
+    }
+
+    fn get_vertical_offset(&self, _time_ellapsed: &Duration) -> f32 {
+        TELEPORTER_HEIGHT_OFFSET
+    }
+
+    fn get_size(&self) -> f32 {
+        self.entity.size * TELEPORTER_DRAW_SIZE_MOD
+    }
+
+    fn get_texture(&self, _time_ellapsed: &Duration) -> TextureId {
+        TextureId::Projectile
+    }
+
+    fn get_tint(&self) -> Color {
+        if self.is_ready() {
+            TELEPORTER_TINT
+        } else {
+            TELEPORTER_COOLDOWN_TINT
+        }
+    }
+}
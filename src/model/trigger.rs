@@ -0,0 +1,37 @@
+use macroquad::math::Vec2;
+use serde::{Deserialize, Serialize};
+
+use super::{enemy::EnemyType, fog::Fog, Entity, SoundId};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TriggerAction {
+    SpawnEnemy {
+        enemy_type: EnemyType,
+        position: Vec2,
+    },
+    OpenDoor(String),
+    CloseDoor(String),
+    ShowMessage {
+        text: String,
+        duration: f32,
+    },
+    PlaySound(SoundId),
+    /// Sets the light level, 1.0 is the default brightness
+    ChangeLighting(f32),
+    ChangeFog(Fog),
+    Teleport(Vec2),
+    EndLevel,
+}
+
+/// Volume that runs its actions when the player walks into it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trigger {
+    /// Passed to the level script when the trigger fires
+    pub name: Option<String>,
+    pub entity: Entity,
+    pub actions: Vec<TriggerAction>,
+    /// Fires only the first time the player enters
+    pub once: bool,
+    pub has_fired: bool,
+    pub is_player_inside: bool,
+}
@@ -1,17 +1,27 @@
 use crate::{
     constants::{
         CEILING_COLOR, DEBUG_DRAW_DELAY_MS, DEBUG_INITAL_DRAW_DELAY_MS, ENTER_DEBUG_MODE_KEY,
-        EXIT_DEBUG_MODE_KEY, FLOOR_COLOR, GAME_OVER_TEXT, GAME_OVER_TEXT_SIZE, GAME_WON_TEXT,
+        EXIT_DEBUG_MODE_KEY, FLOOR_COLOR, GAME_OVER_HIGH_SCORE_TEXT_SIZE,
+        GAME_OVER_SCORE_TEXT_SIZE, GAME_OVER_TEXT, GAME_OVER_TEXT_SIZE, GAME_WON_TEXT,
         GAME_WON_TEXT_SIZE, GAME_WON_TIME_TEXT_SIZE, GAME_WON_TIME_TEXT_X_OFFSET,
-        LEVEL_WON_NEXT_LEVEL_TEXT, LEVEL_WON_NEXT_LEVEL_TEXT_SIZE, LEVEL_WON_TEXT,
-        LEVEL_WON_TEXT_SIZE, LEVEL_WON_TIME_TEXT_SIZE, LEVEL_WON_TIME_TEXT_X_OFFSET,
-        TRY_AGAIN_TEXT, TRY_AGAIN_TEXT_SIZE, TRY_AGAIN_WON_TEXT, TRY_AGAIN_WON_TEXT_SIZE,
+        LEADERBOARD_CLOSE_HINT_TEXT, LEADERBOARD_ENTRY_TEXT_SIZE, LEADERBOARD_HINT_TEXT,
+        LEADERBOARD_HINT_TEXT_SIZE, LEADERBOARD_NEW_RECORD_TEXT_SIZE, LEADERBOARD_TEXT_SIZE,
+        LEVEL_WON_NEXT_LEVEL_TEXT, LEVEL_WON_NEXT_LEVEL_TEXT_SIZE, LEVEL_WON_STATS_TEXT_SIZE,
+        LEVEL_WON_TEXT, LEVEL_WON_TEXT_SIZE, LEVEL_WON_TIME_TEXT_SIZE,
+        LEVEL_WON_TIME_TEXT_X_OFFSET, RESTART_LEVEL_TEXT, SELECT_DIFFICULTY_HINT_TEXT,
+        SELECT_DIFFICULTY_HINT_TEXT_SIZE, SELECT_DIFFICULTY_OPTION_TEXT_SIZE,
+        SELECT_DIFFICULTY_TEXT, SELECT_DIFFICULTY_TEXT_SIZE, TRY_AGAIN_TEXT, TRY_AGAIN_TEXT_SIZE,
+        TRY_AGAIN_WON_TEXT, TRY_AGAIN_WON_TEXT_SIZE,
     },
     draw::Drawable,
     file_loaders::texture_manager::TextureManager,
+    model::{
+        difficulty::Difficulty, game_mode::GameMode, leaderboard::Leaderboard,
+        level_stats::LevelStats, survival::SurvivalResult,
+    },
 };
 use macroquad::{
-    color::{BLACK, DARKBLUE, ORANGE, RED, WHITE},
+    color::{BLACK, DARKBLUE, DARKGRAY, GRAY, ORANGE, RED, WHITE, YELLOW},
     input::{is_key_pressed, is_key_released},
     miniquad::window::screen_size,
     shapes::draw_rectangle,
@@ -31,8 +41,8 @@ fn draw_bg(screen_size: (f32, f32)) {
     );
 }
 
-fn sort_drawables(to_draw: &[Box<dyn Drawable>]) -> Vec<&Box<dyn Drawable>> {
-    let mut draw_in_order: Vec<_> = to_draw.iter().collect();
+fn sort_drawables(to_draw: &[Box<dyn Drawable>]) -> Vec<&dyn Drawable> {
+    let mut draw_in_order: Vec<_> = to_draw.iter().map(|d| d.as_ref()).collect();
     draw_in_order.sort_by(|a, b| b.get_z_index().total_cmp(&a.get_z_index()));
     draw_in_order
 }
@@ -40,7 +50,7 @@ fn sort_drawables(to_draw: &[Box<dyn Drawable>]) -> Vec<&Box<dyn Drawable>> {
 async fn default_renderer(
     texture_manager: &TextureManager,
     screen: (f32, f32),
-    draw_in_order: &[&Box<dyn Drawable>],
+    draw_in_order: &[&dyn Drawable],
 ) {
     draw_bg(screen);
     for d in draw_in_order {
@@ -52,7 +62,7 @@ async fn default_renderer(
 async fn debug_renderer(
     texture_manager: &TextureManager,
     screen: (f32, f32),
-    draw_in_order: &[&Box<dyn Drawable>],
+    draw_in_order: &[&dyn Drawable],
 ) {
     let initial_delay = Duration::from_millis(DEBUG_INITAL_DRAW_DELAY_MS);
     let sleep_duration = Duration::from_millis(DEBUG_DRAW_DELAY_MS);
@@ -94,7 +104,96 @@ pub async fn render_drawables(texture_manager: &TextureManager, to_draw: &[Box<d
     };
 }
 
-pub async fn render_game_over() {
+pub async fn render_select_difficulty(selected: Difficulty, mode: GameMode) {
+    let screen = screen_size();
+    clear_background(DARKGRAY);
+
+    let x1 =
+        (0.5 - SELECT_DIFFICULTY_TEXT.len() as f32 * SELECT_DIFFICULTY_TEXT_SIZE * 0.25) * screen.0;
+    draw_text(
+        SELECT_DIFFICULTY_TEXT,
+        x1,
+        0.25 * screen.1,
+        SELECT_DIFFICULTY_TEXT_SIZE * screen.0,
+        WHITE,
+    );
+
+    for (i, difficulty) in Difficulty::ALL.iter().enumerate() {
+        let text = format!("{:?}", difficulty);
+        let color = if *difficulty == selected {
+            YELLOW
+        } else {
+            GRAY
+        };
+        let x = (0.5 - text.len() as f32 * SELECT_DIFFICULTY_OPTION_TEXT_SIZE * 0.25) * screen.0;
+        draw_text(
+            &text,
+            x,
+            (0.45 + i as f32 * 0.1) * screen.1,
+            SELECT_DIFFICULTY_OPTION_TEXT_SIZE * screen.0,
+            color,
+        );
+    }
+
+    let mode_text = format!("< {:?} >", mode);
+    let x_mode =
+        (0.5 - mode_text.len() as f32 * SELECT_DIFFICULTY_OPTION_TEXT_SIZE * 0.25) * screen.0;
+    draw_text(
+        &mode_text,
+        x_mode,
+        0.35 * screen.1,
+        SELECT_DIFFICULTY_OPTION_TEXT_SIZE * screen.0,
+        WHITE,
+    );
+
+    let x2 = (0.5
+        - SELECT_DIFFICULTY_HINT_TEXT.len() as f32 * SELECT_DIFFICULTY_HINT_TEXT_SIZE * 0.25)
+        * screen.0;
+    draw_text(
+        SELECT_DIFFICULTY_HINT_TEXT,
+        x2,
+        0.85 * screen.1,
+        SELECT_DIFFICULTY_HINT_TEXT_SIZE * screen.0,
+        WHITE,
+    );
+    draw_centered_hint(LEADERBOARD_HINT_TEXT, screen);
+
+    next_frame().await;
+}
+
+fn draw_survival_result(survival_result: &SurvivalResult, screen: (f32, f32)) {
+    let score_text = format!(
+        "Wave: {} Score: {}",
+        survival_result.wave, survival_result.score
+    );
+    let x = (0.5 - score_text.len() as f32 * GAME_OVER_SCORE_TEXT_SIZE * 0.25) * screen.0;
+    draw_text(
+        &score_text,
+        x,
+        0.15 * screen.1,
+        GAME_OVER_SCORE_TEXT_SIZE * screen.0,
+        WHITE,
+    );
+
+    let high_scores_text = survival_result
+        .high_scores
+        .iter()
+        .enumerate()
+        .map(|(i, score)| format!("{}. {}", i + 1, score))
+        .collect::<Vec<_>>()
+        .join("  ");
+    let x =
+        (0.5 - high_scores_text.len() as f32 * GAME_OVER_HIGH_SCORE_TEXT_SIZE * 0.25) * screen.0;
+    draw_text(
+        &high_scores_text,
+        x,
+        0.25 * screen.1,
+        GAME_OVER_HIGH_SCORE_TEXT_SIZE * screen.0,
+        WHITE,
+    );
+}
+
+pub async fn render_game_over(survival_result: Option<&SurvivalResult>, has_checkpoint: bool) {
     let screen = screen_size();
     clear_background(RED);
 
@@ -117,13 +216,52 @@ pub async fn render_game_over() {
         WHITE,
     );
 
+    if let Some(survival_result) = survival_result {
+        draw_survival_result(survival_result, screen);
+    }
+    if has_checkpoint {
+        draw_centered_hint(RESTART_LEVEL_TEXT, screen);
+    }
+
     next_frame().await;
 }
 
-fn time_to_text(time: Duration) -> String {
+fn draw_centered_hint(text: &str, screen: (f32, f32)) {
+    let x = (0.5 - text.len() as f32 * LEADERBOARD_HINT_TEXT_SIZE * 0.25) * screen.0;
+    draw_text(
+        text,
+        x,
+        0.93 * screen.1,
+        LEADERBOARD_HINT_TEXT_SIZE * screen.0,
+        WHITE,
+    );
+}
+
+/// Formats seconds since the Unix epoch as a YYYY-MM-DD date
+fn date_to_text(seconds: u64) -> String {
+    // Civil from days, see https://howardhinnant.github.io/date_algorithms.html
+    let days = (seconds / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+pub fn time_to_text(time: Duration) -> String {
     let minutes = time.as_secs() / 60;
     let secs = time.as_secs() % 60;
-    format!("{:02}:{:02}", minutes, secs)
+    format!("{:02}:{:02}.{:03}", minutes, secs, time.subsec_millis())
 }
 
 pub async fn render_game_won(time: Duration, best_time: Duration) {
@@ -167,7 +305,99 @@ pub async fn render_game_won(time: Duration, best_time: Duration) {
     next_frame().await;
 }
 
-pub async fn render_level_won(time: Duration, best_time: Duration) {
+fn draw_level_stats(stats: &LevelStats, screen: (f32, f32)) {
+    let lines = [
+        format!(
+            "Kills: {}/{}  Secrets: {}/{}",
+            stats.kills, stats.total_enemies, stats.secrets_found, stats.total_secrets
+        ),
+        format!(
+            "Hits: {}/{} ({:.0}%)  Damage taken: {:.0}",
+            stats.shots_hit,
+            stats.shots_fired,
+            stats.get_accuracy(),
+            stats.damage_taken
+        ),
+    ];
+
+    for (i, line) in lines.iter().enumerate() {
+        let x = (0.5 - line.len() as f32 * LEVEL_WON_STATS_TEXT_SIZE * 0.25) * screen.0;
+        draw_text(
+            line,
+            x,
+            (0.68 + i as f32 * 0.07) * screen.1,
+            LEVEL_WON_STATS_TEXT_SIZE * screen.0,
+            WHITE,
+        );
+    }
+}
+
+pub async fn render_leaderboard(leaderboard: &Leaderboard) {
+    let screen = screen_size();
+    clear_background(DARKGRAY);
+
+    let title = format!(
+        "Level {} leaderboard ({:?})",
+        leaderboard.level, leaderboard.difficulty
+    );
+    let x = (0.5 - title.len() as f32 * LEADERBOARD_TEXT_SIZE * 0.25) * screen.0;
+    draw_text(
+        &title,
+        x,
+        0.15 * screen.1,
+        LEADERBOARD_TEXT_SIZE * screen.0,
+        WHITE,
+    );
+
+    let lines: Vec<_> = if leaderboard.entries.is_empty() {
+        vec!["No records yet".to_string()]
+    } else {
+        leaderboard
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                format!(
+                    "{:>2}. {:<12} {}  {}  Kills {}/{}  Secrets {}/{}",
+                    i + 1,
+                    entry.name,
+                    time_to_text(entry.time),
+                    date_to_text(entry.date),
+                    entry.stats.kills,
+                    entry.stats.total_enemies,
+                    entry.stats.secrets_found,
+                    entry.stats.total_secrets
+                )
+            })
+            .collect()
+    };
+
+    for (i, line) in lines.iter().enumerate() {
+        let color = if leaderboard.new_record == Some(i) {
+            YELLOW
+        } else {
+            WHITE
+        };
+        draw_text(
+            line,
+            0.1 * screen.0,
+            (0.27 + i as f32 * 0.06) * screen.1,
+            LEADERBOARD_ENTRY_TEXT_SIZE * screen.0,
+            color,
+        );
+    }
+
+    draw_centered_hint(LEADERBOARD_CLOSE_HINT_TEXT, screen);
+
+    next_frame().await;
+}
+
+pub async fn render_level_won(
+    time: Duration,
+    best_time: Duration,
+    stats: &LevelStats,
+    new_record: Option<usize>,
+) {
     let screen = screen_size();
     clear_background(DARKBLUE);
 
@@ -187,17 +417,30 @@ pub async fn render_level_won(time: Duration, best_time: Duration) {
     draw_text(
         LEVEL_WON_TEXT,
         x1,
-        0.4 * screen.1,
+        0.35 * screen.1,
         LEVEL_WON_TEXT_SIZE * screen.0,
         WHITE,
     );
     draw_text(
         &time_text,
         x2,
-        0.6 * screen.1,
+        0.55 * screen.1,
         LEVEL_WON_TIME_TEXT_SIZE * screen.0,
         WHITE,
     );
+    draw_level_stats(stats, screen);
+    if let Some(position) = new_record {
+        let record_text = format!("New record! #{}", position + 1);
+        let x =
+            (0.5 - record_text.len() as f32 * LEADERBOARD_NEW_RECORD_TEXT_SIZE * 0.25) * screen.0;
+        draw_text(
+            &record_text,
+            x,
+            0.18 * screen.1,
+            LEADERBOARD_NEW_RECORD_TEXT_SIZE * screen.0,
+            YELLOW,
+        );
+    }
     draw_text(
         LEVEL_WON_NEXT_LEVEL_TEXT,
         x3,
@@ -205,6 +448,23 @@ pub async fn render_level_won(time: Duration, best_time: Duration) {
         LEVEL_WON_NEXT_LEVEL_TEXT_SIZE * screen.0,
         WHITE,
     );
+    draw_centered_hint(LEADERBOARD_HINT_TEXT, screen);
 
     next_frame().await;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_to_text() {
+        assert_eq!(date_to_text(0), "1970-01-01");
+        // Leap day and the day after it
+        assert_eq!(date_to_text(1_709_164_800), "2024-02-29");
+        assert_eq!(date_to_text(1_709_251_200), "2024-03-01");
+        // Last second of a year and the first of the next
+        assert_eq!(date_to_text(1_704_067_199), "2023-12-31");
+        assert_eq!(date_to_text(1_704_067_200), "2024-01-01");
+    }
+}
@@ -0,0 +1,380 @@
+use std::{
+    cell::RefCell, collections::HashSet, error::Error, fs::read_to_string, mem::take, path::Path,
+    rc::Rc,
+};
+
+use macroquad::math::{vec2, vec3, Vec2};
+use rhai::{
+    serde::{from_dynamic, to_dynamic},
+    CallFnOptions, Dynamic, Engine, EvalAltResult, FuncArgs, Map, Scope, AST, FLOAT, INT,
+};
+use serde::de::DeserializeOwned;
+use serde_json::{from_value, Value};
+
+use crate::{
+    constants::{
+        LEVEL_PATH, SCRIPT_EXTENSION, SCRIPT_MAX_CALL_LEVELS, SCRIPT_MAX_COLLECTION_SIZE,
+        SCRIPT_MAX_OPERATIONS, SCRIPT_MAX_STRING_SIZE,
+    },
+    model::{enemy::EnemyType, fog::Fog, GameEvent, GameObjects, SoundId},
+};
+
+/// Read-only view of the game the script API answers queries from
+#[derive(Debug, Clone, Default)]
+struct ScriptSnapshot {
+    player_position: Vec2,
+    player_health: f32,
+    picked_up_keys: usize,
+    keys_left: usize,
+    enemy_count: usize,
+    level_time: f32,
+    open_doors: Vec<String>,
+}
+impl From<&GameObjects> for ScriptSnapshot {
+    fn from(game_objects: &GameObjects) -> Self {
+        Self {
+            player_position: game_objects.player.entity.position,
+            player_health: game_objects.player_info.health,
+            picked_up_keys: game_objects.player_info.picked_up_keys,
+            keys_left: game_objects.keys.len(),
+            enemy_count: game_objects.enemies.len(),
+            level_time: game_objects.level_time,
+            open_doors: game_objects
+                .doors
+                .iter()
+                .filter(|door| door.is_open)
+                .map(|door| door.name.clone())
+                .collect(),
+        }
+    }
+}
+
+type SharedSnapshot = Rc<RefCell<ScriptSnapshot>>;
+type SharedEvents = Rc<RefCell<Vec<GameEvent>>>;
+
+/// Parses names like "Melee" or "ShotHit" into the matching enum variant
+fn parse_name<T: DeserializeOwned>(name: &str) -> Result<T, Box<EvalAltResult>> {
+    from_value(Value::String(name.to_string()))
+        .map_err(|_| format!("Unknown name: {}", name).into())
+}
+
+fn register_queries(engine: &mut Engine, snapshot: &SharedSnapshot) {
+    let s = snapshot.clone();
+    engine.register_fn("player_x", move || s.borrow().player_position.x as FLOAT);
+    let s = snapshot.clone();
+    engine.register_fn("player_y", move || s.borrow().player_position.y as FLOAT);
+    let s = snapshot.clone();
+    engine.register_fn("player_health", move || s.borrow().player_health as FLOAT);
+    let s = snapshot.clone();
+    engine.register_fn("keys_picked_up", move || s.borrow().picked_up_keys as INT);
+    let s = snapshot.clone();
+    engine.register_fn("keys_left", move || s.borrow().keys_left as INT);
+    let s = snapshot.clone();
+    engine.register_fn("enemy_count", move || s.borrow().enemy_count as INT);
+    let s = snapshot.clone();
+    engine.register_fn("level_time", move || s.borrow().level_time as FLOAT);
+    let s = snapshot.clone();
+    engine.register_fn("is_door_open", move |name: &str| {
+        s.borrow().open_doors.iter().any(|door| door == name)
+    });
+}
+
+fn register_actions(engine: &mut Engine, events: &SharedEvents) {
+    let e = events.clone();
+    engine.register_fn(
+        "spawn_enemy",
+        move |enemy_type: &str, x: FLOAT, y: FLOAT| -> Result<(), Box<EvalAltResult>> {
+            let enemy_type: EnemyType = parse_name(enemy_type)?;
+            e.borrow_mut().push(GameEvent::SpawnEnemy {
+                position: vec2(x as f32, y as f32),
+                enemy_type,
+            });
+            Ok(())
+        },
+    );
+    let e = events.clone();
+    engine.register_fn("open_door", move |name: &str| {
+        e.borrow_mut().push(GameEvent::OpenDoor(name.to_string()))
+    });
+    let e = events.clone();
+    engine.register_fn("close_door", move |name: &str| {
+        e.borrow_mut().push(GameEvent::CloseDoor(name.to_string()))
+    });
+    let e = events.clone();
+    engine.register_fn("show_message", move |text: &str, duration: FLOAT| {
+        e.borrow_mut().push(GameEvent::ShowMessage {
+            text: text.to_string(),
+            duration: duration as f32,
+        })
+    });
+    let e = events.clone();
+    engine.register_fn(
+        "play_sound",
+        move |sound: &str| -> Result<(), Box<EvalAltResult>> {
+            let sound: SoundId = parse_name(sound)?;
+            e.borrow_mut().push(GameEvent::PlaySound(sound));
+            Ok(())
+        },
+    );
+    let e = events.clone();
+    engine.register_fn("set_lighting", move |light_level: FLOAT| {
+        e.borrow_mut()
+            .push(GameEvent::ChangeLighting(light_level as f32))
+    });
+    let e = events.clone();
+    engine.register_fn(
+        "set_fog",
+        move |r: FLOAT, g: FLOAT, b: FLOAT, density: FLOAT| {
+            e.borrow_mut().push(GameEvent::ChangeFog(Fog {
+                color: vec3(r as f32, g as f32, b as f32),
+                density: density as f32,
+            }))
+        },
+    );
+    let e = events.clone();
+    engine.register_fn("teleport_player", move |x: FLOAT, y: FLOAT| {
+        e.borrow_mut().push(GameEvent::TeleportPlayer {
+            position: vec2(x as f32, y as f32),
+            look: None,
+        })
+    });
+    let e = events.clone();
+    engine.register_fn("damage_player", move |damage: FLOAT| {
+        e.borrow_mut()
+            .push(GameEvent::PlayerTakeDamage(damage as f32))
+    });
+    let e = events.clone();
+    engine.register_fn("end_level", move || {
+        e.borrow_mut().push(GameEvent::EndLevel)
+    });
+}
+
+fn create_engine(snapshot: &SharedSnapshot, events: &SharedEvents) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(SCRIPT_MAX_OPERATIONS);
+    engine.set_max_call_levels(SCRIPT_MAX_CALL_LEVELS);
+    engine.set_max_string_size(SCRIPT_MAX_STRING_SIZE);
+    engine.set_max_array_size(SCRIPT_MAX_COLLECTION_SIZE);
+    engine.set_max_map_size(SCRIPT_MAX_COLLECTION_SIZE);
+    engine.on_print(|text| println!("Script: {}", text));
+
+    register_queries(&mut engine, snapshot);
+    register_actions(&mut engine, events);
+    engine
+}
+
+/// Level logic written in Rhai. Scripts define any of the hooks `init`, `on_tick`,
+/// `on_enemy_killed`, `on_key_picked_up` and `on_trigger`, and keep their state in `this`.
+pub struct LevelScript {
+    engine: Engine,
+    ast: AST,
+    hooks: HashSet<String>,
+    state: Dynamic,
+    snapshot: SharedSnapshot,
+    events: SharedEvents,
+}
+impl LevelScript {
+    pub fn from_source(source: &str) -> Result<Self, Box<dyn Error>> {
+        let snapshot = SharedSnapshot::default();
+        let events = SharedEvents::default();
+        let engine = create_engine(&snapshot, &events);
+        let ast = engine.compile(source)?;
+        let hooks = ast
+            .iter_functions()
+            .map(|function| function.name.to_string())
+            .collect();
+
+        let mut script = Self {
+            engine,
+            ast,
+            hooks,
+            state: Map::new().into(),
+            snapshot,
+            events,
+        };
+        script.call_hook("init", ());
+        Ok(script)
+    }
+
+    pub fn load(level_number: u32) -> Option<Self> {
+        let script_path = format!("{LEVEL_PATH}level{level_number}.{SCRIPT_EXTENSION}");
+        if !Path::new(&script_path).exists() {
+            return None;
+        }
+
+        let source = match read_to_string(&script_path) {
+            Ok(source) => source,
+            Err(err) => {
+                println!("Error reading script {}: {}", script_path, err);
+                return None;
+            }
+        };
+
+        match Self::from_source(&source) {
+            Ok(script) => {
+                println!("Loaded script: {}", script_path);
+                Some(script)
+            }
+            Err(err) => {
+                println!("Error compiling script {}: {}", script_path, err);
+                None
+            }
+        }
+    }
+
+    /// Script state in a form that can be written to a save file
+    pub fn get_state(&self) -> Option<Value> {
+        from_dynamic(&self.state)
+            .map_err(|err| println!("Error saving script state: {}", err))
+            .ok()
+    }
+
+    pub fn set_state(&mut self, state: &Value) {
+        match to_dynamic(state) {
+            Ok(state) => self.state = state,
+            Err(err) => println!("Error loading script state: {}", err),
+        }
+    }
+
+    fn call_hook(&mut self, name: &str, args: impl FuncArgs) {
+        if !self.hooks.contains(name) {
+            return;
+        }
+
+        let options = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut self.state);
+        let result = self.engine.call_fn_with_options::<Dynamic>(
+            options,
+            &mut Scope::new(),
+            &self.ast,
+            name,
+            args,
+        );
+
+        if let Err(err) = result {
+            println!("Error in script hook {}: {}", name, err);
+        }
+    }
+
+    /// Runs `on_tick` and the hooks for this step's events, returning the events the script emitted.
+    pub fn run_hooks(
+        &mut self,
+        game_objects: &GameObjects,
+        events: &[GameEvent],
+        delta: f32,
+    ) -> Vec<GameEvent> {
+        *self.snapshot.borrow_mut() = game_objects.into();
+
+        self.call_hook("on_tick", (delta as FLOAT,));
+        self.call_event_hooks(events);
+
+        take(&mut *self.events.borrow_mut())
+    }
+
+    /// Runs the hooks for events caused after `run_hooks`, like kills by an explosion
+    pub fn run_event_hooks(
+        &mut self,
+        game_objects: &GameObjects,
+        events: &[GameEvent],
+    ) -> Vec<GameEvent> {
+        *self.snapshot.borrow_mut() = game_objects.into();
+        self.call_event_hooks(events);
+
+        take(&mut *self.events.borrow_mut())
+    }
+
+    fn call_event_hooks(&mut self, events: &[GameEvent]) {
+        for event in events {
+            match event {
+                GameEvent::EnemyKilled {
+                    position,
+                    enemy_type,
+                    ..
+                } => self.call_hook(
+                    "on_enemy_killed",
+                    (
+                        format!("{:?}", enemy_type),
+                        position.x as FLOAT,
+                        position.y as FLOAT,
+                    ),
+                ),
+                GameEvent::PickUpKey => self.call_hook("on_key_picked_up", ()),
+                GameEvent::TriggerFired(name) => self.call_hook("on_trigger", (name.clone(),)),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_hooks() {
+        let source = r#"
+            fn init() {
+                this.kills = 0;
+            }
+
+            fn on_enemy_killed(enemy_type, x, y) {
+                this.kills += 1;
+                if this.kills == 2 {
+                    open_door("gate");
+                }
+            }
+
+            fn on_trigger(name) {
+                if name == "ambush" {
+                    spawn_enemy("Kamikaze", player_x() + 2.0, player_y());
+                }
+            }
+        "#;
+        let mut script = LevelScript::from_source(source).unwrap();
+        let game_objects = GameObjects::default();
+        let kill = GameEvent::EnemyKilled {
+            position: vec2(0.0, 0.0),
+            enemy_type: EnemyType::Melee,
+            name: None,
+        };
+
+        let events = script.run_hooks(&game_objects, std::slice::from_ref(&kill), 0.1);
+        assert!(events.is_empty());
+
+        let events = script.run_hooks(
+            &game_objects,
+            &[kill.clone(), GameEvent::TriggerFired("ambush".to_string())],
+            0.1,
+        );
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], GameEvent::OpenDoor(name) if name == "gate"));
+        assert!(matches!(
+            events[1],
+            GameEvent::SpawnEnemy {
+                enemy_type: EnemyType::Kamikaze,
+                ..
+            }
+        ));
+
+        // Kills by explosions arrive after the step's hooks have run
+        let events = script.run_event_hooks(&game_objects, std::slice::from_ref(&kill));
+        assert!(events.is_empty());
+        assert_eq!(script.get_state(), Some(serde_json::json!({ "kills": 3 })));
+    }
+
+    #[test]
+    fn test_script_errors_do_not_stop_the_game() {
+        let source = r#"
+            fn on_tick(delta) {
+                spawn_enemy("Dragon", 0.0, 0.0);
+            }
+        "#;
+        let mut script = LevelScript::from_source(source).unwrap();
+
+        let events = script.run_hooks(&GameObjects::default(), &[], 0.1);
+
+        assert!(events.is_empty());
+        assert!(LevelScript::from_source("fn broken( {").is_err());
+    }
+}
@@ -1,6 +1,19 @@
+pub mod automap;
+pub mod boss;
+pub mod checkpoint;
+pub mod door;
 pub mod enemy;
+pub mod explosion;
+pub mod hazard;
 pub mod id_generator;
 pub mod key;
+pub mod light;
+pub mod objective;
 pub mod player;
 pub mod projectile;
 pub mod shoot;
+pub mod spawner;
+pub mod stats;
+pub mod survival;
+pub mod teleporter;
+pub mod trigger;
@@ -0,0 +1,131 @@
+use std::collections::HashSet;
+
+use macroquad::math::Vec2;
+
+use crate::{
+    constants::FOV,
+    math::find_intersection,
+    model::{
+        automap::{Automap, AutomapKey},
+        GameObjects, Player, Wall,
+    },
+};
+
+fn is_in_sight(player: &Player, position: Vec2, walls: &[Wall]) -> bool {
+    let to_target = position - player.entity.position;
+    let is_in_view = player.look.angle_between(to_target) <= FOV / 2.0;
+
+    is_in_view
+        && !walls.iter().filter(|wall| !wall.is_masked).any(|wall| {
+            find_intersection(player.entity.position, position, wall.start, wall.end).is_some()
+        })
+}
+
+/// Reveals the walls the view rays hit and the keys and exits in sight, marks picked up keys
+pub fn update_automap(
+    automap: Automap,
+    game_objects: &GameObjects,
+    hit_walls: &[usize],
+) -> Automap {
+    let player = &game_objects.player;
+
+    let mut walls = automap.walls;
+    for index in hit_walls.iter().collect::<HashSet<_>>() {
+        let wall = &game_objects.walls[*index];
+        if !walls.contains(&(wall.start, wall.end)) {
+            walls.push((wall.start, wall.end));
+        }
+    }
+
+    let keys = automap
+        .keys
+        .into_iter()
+        .map(|key| AutomapKey {
+            is_collected: key.is_collected
+                || !game_objects
+                    .keys
+                    .iter()
+                    .any(|other| other.entity.position == key.position),
+            ..key
+        })
+        .collect::<Vec<_>>();
+    let new_keys: Vec<_> = game_objects
+        .keys
+        .iter()
+        .filter(|key| !keys.iter().any(|seen| seen.position == key.entity.position))
+        .filter(|key| is_in_sight(player, key.entity.position, &game_objects.walls))
+        .map(|key| AutomapKey {
+            position: key.entity.position,
+            is_collected: false,
+        })
+        .collect();
+
+    let new_exits: Vec<_> = game_objects
+        .exit_triggers
+        .iter()
+        .filter(|exit| !automap.exits.contains(exit))
+        .filter(|exit| is_in_sight(player, exit.position, &game_objects.walls))
+        .copied()
+        .collect();
+
+    Automap {
+        walls,
+        keys: keys.into_iter().chain(new_keys).collect(),
+        exits: automap.exits.into_iter().chain(new_exits).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::vec2;
+
+    use crate::model::{key_object::KeyObject, Entity, TextureId};
+
+    use super::*;
+
+    #[test]
+    fn test_update_automap() {
+        let key = |x: f32, y: f32| KeyObject {
+            entity: Entity {
+                position: vec2(x, y),
+                size: 0.5,
+            },
+        };
+        let mut game_objects = GameObjects {
+            player: Player {
+                entity: Entity {
+                    position: vec2(0.0, 0.0),
+                    size: 0.5,
+                },
+                look: vec2(1.0, 0.0),
+            },
+            walls: vec![Wall {
+                texture: TextureId::Stone,
+                start: vec2(5.0, -5.0),
+                end: vec2(5.0, 5.0),
+                is_masked: false,
+                shots_pass_through: false,
+                door: None,
+            }],
+            // In sight, behind the wall and behind the player
+            keys: vec![key(3.0, 1.0), key(7.0, 0.0), key(-3.0, 0.0)],
+            ..Default::default()
+        };
+
+        let automap = update_automap(Automap::default(), &game_objects, &[0, 0]);
+        assert_eq!(automap.walls, vec![(vec2(5.0, -5.0), vec2(5.0, 5.0))]);
+        assert_eq!(
+            automap.keys,
+            vec![AutomapKey {
+                position: vec2(3.0, 1.0),
+                is_collected: false,
+            }]
+        );
+
+        game_objects.keys.remove(0);
+        let automap = update_automap(automap, &game_objects, &[0]);
+        assert_eq!(automap.walls.len(), 1);
+        assert_eq!(automap.keys.len(), 1);
+        assert!(automap.keys[0].is_collected);
+    }
+}
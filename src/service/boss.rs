@@ -0,0 +1,200 @@
+use macroquad::math::Vec2;
+
+use crate::{
+    constants::{
+        BOSS_CHARGE_ATTACK_DELAY, BOSS_CHARGE_DAMAGE, BOSS_CHARGE_SPEED, BOSS_CHARGE_TIME,
+        BOSS_SHOT_SPEED, BOSS_SPREAD_ANGLE, BOSS_SPREAD_ATTACK_DELAY, BOSS_SPREAD_PROJECTILES,
+        BOSS_SUMMON_ATTACK_DELAY, BOSS_SUMMON_COUNT, BOSS_SUMMON_DISTANCE,
+    },
+    math::{check_circles_collide, rotate_point},
+    model::{
+        enemy::{BossPhase, Charge, Enemy, EnemyType},
+        Entity, GameEvent, Player, Wall,
+    },
+};
+
+use super::{enemy::get_enemy_intersecting_walls, spawner::create_summon_events};
+
+fn spread_attack(enemy: Enemy, player: &Player) -> (Enemy, Vec<GameEvent>) {
+    let direction = (player.entity.position - enemy.entity.position).normalize_or_zero();
+    let angle_step = BOSS_SPREAD_ANGLE / (BOSS_SPREAD_PROJECTILES - 1) as f32;
+
+    let events = (0..BOSS_SPREAD_PROJECTILES)
+        .map(|i| {
+            let angle = i as f32 * angle_step - BOSS_SPREAD_ANGLE / 2.0;
+            GameEvent::CreateProjectile {
+                position: enemy.entity.position,
+                direction: rotate_point(direction, Vec2::ZERO, angle) * BOSS_SHOT_SPEED,
+                damage: enemy.get_attack_damage(),
+            }
+        })
+        .collect();
+
+    (
+        Enemy {
+            attack_delay: BOSS_SPREAD_ATTACK_DELAY,
+            ..enemy
+        },
+        events,
+    )
+}
+
+fn charge_attack(enemy: Enemy, player: &Player) -> (Enemy, Vec<GameEvent>) {
+    let charge = Charge {
+        direction: (player.entity.position - enemy.entity.position).normalize_or_zero(),
+        time_left: BOSS_CHARGE_TIME,
+    };
+
+    (
+        Enemy {
+            attack_delay: BOSS_CHARGE_ATTACK_DELAY,
+            charge: Some(charge),
+            ..enemy
+        },
+        vec![],
+    )
+}
+
+fn summon_attack(enemy: Enemy) -> (Enemy, Vec<GameEvent>) {
+    let events = create_summon_events(
+        enemy.entity.position,
+        EnemyType::Melee,
+        BOSS_SUMMON_COUNT,
+        BOSS_SUMMON_DISTANCE,
+    );
+
+    (
+        Enemy {
+            attack_delay: BOSS_SUMMON_ATTACK_DELAY,
+            ..enemy
+        },
+        events,
+    )
+}
+
+/// Picks the attack based on how much health the boss has left.
+pub fn boss_attack_player(enemy: Enemy, player: &Player) -> (Enemy, Vec<GameEvent>) {
+    match BossPhase::for_health_ratio(enemy.get_health_ratio()) {
+        BossPhase::Spread => spread_attack(enemy, player),
+        BossPhase::Charge => charge_attack(enemy, player),
+        BossPhase::Summon => summon_attack(enemy),
+    }
+}
+
+/// Moves a charging boss in a straight line until it hits a wall or the charge runs out.
+pub fn move_charging_boss(enemy: Enemy, walls: &[Wall], delta: f32) -> Enemy {
+    let Some(charge) = enemy.charge else {
+        return enemy;
+    };
+
+    let new_entity = Entity {
+        position: enemy.entity.position + charge.direction * BOSS_CHARGE_SPEED * delta,
+        ..enemy.entity
+    };
+    let time_left = charge.time_left - delta;
+
+    if !get_enemy_intersecting_walls(&new_entity, walls).is_empty() {
+        return Enemy {
+            charge: None,
+            ..enemy
+        };
+    }
+
+    Enemy {
+        entity: new_entity,
+        charge: (time_left > 0.0).then_some(Charge {
+            time_left,
+            ..charge
+        }),
+        ..enemy
+    }
+}
+
+/// Ends the charge and damages the player if the charging boss runs into them.
+pub fn charging_boss_hit_player(enemy: Enemy, player: &Player) -> (Enemy, Vec<GameEvent>) {
+    if !check_circles_collide(
+        player.entity.position,
+        player.entity.size,
+        enemy.entity.position,
+        enemy.entity.size,
+    ) {
+        return (enemy, vec![]);
+    }
+
+    let damage = BOSS_CHARGE_DAMAGE * enemy.difficulty.get_enemy_damage_modifier();
+    (
+        Enemy {
+            charge: None,
+            ..enemy
+        },
+        vec![GameEvent::PlayerTakeDamage(damage)],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::vec2;
+
+    use crate::{model::difficulty::Difficulty, model::TextureId};
+
+    use super::*;
+
+    #[test]
+    fn test_boss_attack_phases() {
+        let player = Player::at(vec2(5.0, 0.0));
+        let boss = EnemyType::Boss.to_enemy(vec2(0.0, 0.0), Difficulty::Normal);
+
+        let (_, events) = boss_attack_player(boss.clone(), &player);
+        assert_eq!(events.len(), BOSS_SPREAD_PROJECTILES);
+
+        let wounded = Enemy {
+            hp: boss.max_hp * 0.5,
+            ..boss.clone()
+        };
+        let (charging, events) = boss_attack_player(wounded, &player);
+        assert!(events.is_empty());
+        assert_eq!(charging.charge.unwrap().direction, vec2(1.0, 0.0));
+
+        let dying = Enemy {
+            hp: boss.max_hp * 0.1,
+            ..boss
+        };
+        let (_, events) = boss_attack_player(dying, &player);
+        assert_eq!(events.len(), BOSS_SUMMON_COUNT);
+        assert!(events
+            .iter()
+            .all(|e| matches!(e, GameEvent::SpawnEnemy { .. })));
+    }
+
+    #[test]
+    fn test_charge_stops_at_wall() {
+        let boss = Enemy {
+            charge: Some(Charge {
+                direction: vec2(1.0, 0.0),
+                time_left: BOSS_CHARGE_TIME,
+            }),
+            ..EnemyType::Boss.to_enemy(vec2(0.0, 0.0), Difficulty::Normal)
+        };
+        let walls = vec![Wall {
+            texture: TextureId::Debug,
+            start: vec2(1.5, -5.0),
+            end: vec2(1.5, 5.0),
+            is_masked: false,
+            shots_pass_through: false,
+            door: None,
+        }];
+
+        let moved = move_charging_boss(boss.clone(), &[], 0.1);
+        assert!(moved.entity.position.x > boss.entity.position.x);
+        assert!(moved.charge.is_some());
+
+        let stopped = move_charging_boss(boss.clone(), &walls, 0.1);
+        assert_eq!(stopped.entity.position, boss.entity.position);
+        assert!(stopped.charge.is_none());
+
+        let player = Player::at(vec2(0.5, 0.0));
+        let (hit, events) = charging_boss_hit_player(boss, &player);
+        assert!(hit.charge.is_none());
+        assert_eq!(events.len(), 1);
+    }
+}
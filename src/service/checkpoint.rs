@@ -0,0 +1,64 @@
+use crate::{
+    math::check_circles_collide,
+    model::{checkpoint::Checkpoint, GameEvent, Player},
+};
+
+pub fn update_checkpoints(
+    player: &Player,
+    checkpoints: Vec<Checkpoint>,
+) -> (Vec<Checkpoint>, Vec<GameEvent>) {
+    let (checkpoints, events): (Vec<_>, Vec<_>) = checkpoints
+        .into_iter()
+        .map(|checkpoint| {
+            let is_touched = check_circles_collide(
+                checkpoint.entity.position,
+                checkpoint.entity.size,
+                player.entity.position,
+                player.entity.size,
+            );
+            if checkpoint.is_reached || !is_touched {
+                return (checkpoint, None);
+            }
+
+            (
+                Checkpoint {
+                    is_reached: true,
+                    ..checkpoint
+                },
+                Some(GameEvent::CheckpointReached),
+            )
+        })
+        .unzip();
+
+    (checkpoints, events.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::vec2;
+
+    use crate::model::Entity;
+
+    use super::*;
+
+    #[test]
+    fn test_update_checkpoints() {
+        let checkpoints = vec![Checkpoint {
+            entity: Entity {
+                position: vec2(0.0, 0.0),
+                size: 0.5,
+            },
+            is_reached: false,
+        }];
+
+        let (checkpoints, events) = update_checkpoints(&Player::at(vec2(2.0, 0.0)), checkpoints);
+        assert!(events.is_empty());
+
+        let (checkpoints, events) = update_checkpoints(&Player::at(vec2(0.5, 0.0)), checkpoints);
+        assert!(matches!(events[..], [GameEvent::CheckpointReached]));
+        assert!(checkpoints[0].is_reached);
+
+        let (_, events) = update_checkpoints(&Player::at(vec2(0.0, 0.0)), checkpoints);
+        assert!(events.is_empty());
+    }
+}
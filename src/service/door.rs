@@ -0,0 +1,86 @@
+use crate::{
+    math::line_intersects_circle,
+    model::{enemy::Enemy, Door, Entity, Player, Wall},
+};
+
+fn is_doorway_blocked(door: &Door, player: &Player, enemies: &[Enemy]) -> bool {
+    let overlaps = |entity: &Entity| {
+        door.walls
+            .iter()
+            .any(|wall| line_intersects_circle(wall.start, wall.end, entity.position, entity.size))
+    };
+
+    overlaps(&player.entity) || enemies.iter().any(|enemy| overlaps(&enemy.entity))
+}
+
+/// Closes the doors waiting to close whose doorway is clear
+pub fn close_doors(
+    doors: Vec<Door>,
+    mut walls: Vec<Wall>,
+    player: &Player,
+    enemies: &[Enemy],
+) -> (Vec<Door>, Vec<Wall>) {
+    let doors = doors
+        .into_iter()
+        .map(|door| {
+            if !door.is_closing || is_doorway_blocked(&door, player, enemies) {
+                return door;
+            }
+
+            walls.extend(door.walls.iter().cloned());
+            Door {
+                is_open: false,
+                is_closing: false,
+                ..door
+            }
+        })
+        .collect();
+
+    (doors, walls)
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::vec2;
+
+    use crate::model::{difficulty::Difficulty, enemy::EnemyType, TextureId};
+
+    use super::*;
+
+    #[test]
+    fn test_close_doors() {
+        let door = Door {
+            name: "gate".to_string(),
+            walls: vec![Wall {
+                texture: TextureId::Door,
+                start: vec2(0.0, -1.0),
+                end: vec2(0.0, 1.0),
+                is_masked: false,
+                shots_pass_through: false,
+                door: Some("gate".to_string()),
+            }],
+            is_open: true,
+            is_closing: true,
+        };
+        let enemy = EnemyType::Melee.to_enemy(vec2(0.1, 0.0), Difficulty::Normal);
+
+        let (doors, walls) = close_doors(
+            vec![door.clone()],
+            vec![],
+            &Player::at(vec2(-3.0, 0.0)),
+            &[enemy],
+        );
+        assert_eq!(doors, vec![door.clone()]);
+        assert!(walls.is_empty());
+
+        let (doors, walls) =
+            close_doors(vec![door.clone()], vec![], &Player::at(vec2(0.0, 0.5)), &[]);
+        assert!(doors[0].is_closing);
+        assert!(walls.is_empty());
+
+        let (doors, walls) =
+            close_doors(vec![door.clone()], vec![], &Player::at(vec2(3.0, 0.0)), &[]);
+        assert!(!doors[0].is_open && !doors[0].is_closing);
+        assert_eq!(walls, door.walls);
+    }
+}
@@ -1,8 +1,12 @@
-use macroquad::math::Vec2;
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use macroquad::math::{vec2, Vec2};
+use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
 
 use crate::{
-    constants::{ENEMY_MAX_CHASE_DISTANCE, MOVE_SPEED, RANGED_ENEMY_SHOT_SPEED},
+    constants::{
+        ENEMY_COLLISION_SIZE_MODIFIER, ENEMY_MAX_CHASE_DISTANCE, ENEMY_SEPARATION_ITERATIONS,
+        MOVE_SPEED, RANGED_ENEMY_SHOT_SPEED, SUMMONER_ENEMY_SUMMON_COUNT,
+        SUMMONER_ENEMY_SUMMON_DISTANCE,
+    },
     math::{check_circles_collide, find_intersection, line_intersects_circle},
     model::{
         enemy::{Enemy, EnemyType},
@@ -10,7 +14,12 @@ use crate::{
     },
 };
 
-fn get_enemy_intersecting_walls<'a>(entity: &'a Entity, walls: &'a [Wall]) -> Vec<&'a Wall> {
+use super::{
+    boss::{boss_attack_player, charging_boss_hit_player, move_charging_boss},
+    spawner::create_summon_events,
+};
+
+pub fn get_enemy_intersecting_walls<'a>(entity: &'a Entity, walls: &'a [Wall]) -> Vec<&'a Wall> {
     walls
         .iter()
         .filter(|wall| line_intersects_circle(wall.start, wall.end, entity.position, entity.size))
@@ -57,7 +66,7 @@ fn move_enemy(player: &Player, enemy: Enemy, walls: &[Wall], delta: f32) -> Enem
     if vector_towards_player.length() > ENEMY_MAX_CHASE_DISTANCE {
         return enemy.clone();
     };
-    let speed = enemy.enemy_type.get_movement_speed();
+    let speed = enemy.get_movement_speed();
 
     let move_vector = vector_towards_player.normalize_or_zero() * speed * delta;
     let new_position = enemy.entity.position + move_vector;
@@ -82,45 +91,71 @@ fn move_enemy(player: &Player, enemy: Enemy, walls: &[Wall], delta: f32) -> Enem
     move_enemy_to_sides(player, enemy, walls, wall_directions)
 }
 
+fn update_hit_timers(enemy: Enemy, delta: f32) -> Enemy {
+    Enemy {
+        hit_timer: (enemy.hit_timer - delta).max(0.0),
+        stagger_timer: (enemy.stagger_timer - delta).max(0.0),
+        ..enemy
+    }
+}
+
 fn move_enemy_for_type(player: &Player, enemy: Enemy, walls: &[Wall], delta: f32) -> Enemy {
+    if enemy.is_staggered() {
+        return enemy;
+    }
+
     match &enemy.enemy_type {
-        EnemyType::Melee | EnemyType::MeleeSlow => move_enemy(player, enemy, walls, delta),
-        EnemyType::Ranged => {
+        EnemyType::Melee | EnemyType::MeleeSlow | EnemyType::Kamikaze => {
+            move_enemy(player, enemy, walls, delta)
+        }
+        EnemyType::Ranged | EnemyType::Summoner => {
             if !enemy_can_attack_player(&enemy, player, walls) {
                 move_enemy(player, enemy, walls, delta)
             } else {
                 enemy
             }
         }
+        EnemyType::Boss => {
+            if enemy.charge.is_some() {
+                move_charging_boss(enemy, walls, delta)
+            } else if !enemy_can_attack_player(&enemy, player, walls) {
+                move_enemy(player, enemy, walls, delta)
+            } else {
+                enemy
+            }
+        }
     }
 }
 
+/// Walls that let shots through don't block shooting enemies either, the player can shoot back through them
 fn enemy_can_attack_player(enemy: &Enemy, player: &Player, walls: &[Wall]) -> bool {
     check_circles_collide(
         player.entity.position,
         player.entity.size,
         enemy.entity.position,
         enemy.enemy_type.get_attack_range() + enemy.entity.size,
-    ) && !walls.iter().any(|wall| {
-        find_intersection(
-            enemy.entity.position,
-            player.entity.position,
-            wall.start,
-            wall.end,
-        )
-        .is_some()
-    })
+    ) && !walls
+        .iter()
+        .filter(|wall| !wall.shots_pass_through || !enemy.shoots_through_fences())
+        .any(|wall| {
+            find_intersection(
+                enemy.entity.position,
+                player.entity.position,
+                wall.start,
+                wall.end,
+            )
+            .is_some()
+        })
 }
 
 fn melee_enemy_attack_player(enemy: Enemy) -> (Enemy, Vec<GameEvent>) {
+    let damage_event = GameEvent::PlayerTakeDamage(enemy.get_attack_damage());
     (
         Enemy {
             attack_delay: enemy.enemy_type.get_attack_speed(),
             ..enemy
         },
-        vec![GameEvent::PlayerTakeDamage(
-            enemy.enemy_type.get_attack_damage(),
-        )],
+        vec![damage_event],
     )
 }
 
@@ -129,7 +164,7 @@ fn ranged_enemy_attack_player(enemy: Enemy, player: &Player) -> (Enemy, Vec<Game
         position: enemy.entity.position,
         direction: (player.entity.position - enemy.entity.position).normalize_or_zero()
             * RANGED_ENEMY_SHOT_SPEED,
-        damage: enemy.enemy_type.get_attack_damage(),
+        damage: enemy.get_attack_damage(),
     };
 
     (
@@ -141,6 +176,34 @@ fn ranged_enemy_attack_player(enemy: Enemy, player: &Player) -> (Enemy, Vec<Game
     )
 }
 
+fn summoner_enemy_attack_player(enemy: Enemy) -> (Enemy, Vec<GameEvent>) {
+    let summon_events = create_summon_events(
+        enemy.entity.position,
+        EnemyType::Melee,
+        SUMMONER_ENEMY_SUMMON_COUNT,
+        SUMMONER_ENEMY_SUMMON_DISTANCE,
+    );
+
+    (
+        Enemy {
+            attack_delay: enemy.enemy_type.get_attack_speed(),
+            ..enemy
+        },
+        summon_events,
+    )
+}
+
+/// The enemy dies, and killing it sets off its death explosion
+fn kamikaze_enemy_attack_player(enemy: Enemy) -> (Enemy, Vec<GameEvent>) {
+    let killed_event = GameEvent::EnemyKilled {
+        position: enemy.entity.position,
+        enemy_type: enemy.enemy_type,
+        name: enemy.name.clone(),
+    };
+
+    (Enemy { hp: 0.0, ..enemy }, vec![killed_event])
+}
+
 fn enemy_attack_player(
     player: &Player,
     enemy: Enemy,
@@ -148,17 +211,25 @@ fn enemy_attack_player(
     delta: f32,
 ) -> (Enemy, Vec<GameEvent>) {
     let new_attack_delay = (enemy.attack_delay - delta).max(0.0);
+    let can_attack = enemy_can_attack_player(&enemy, player, walls);
     let updated_enemy = Enemy {
         attack_delay: new_attack_delay,
+        is_engaged: enemy.is_engaged || can_attack,
         ..enemy
     };
 
-    if new_attack_delay > 0.0 || !enemy_can_attack_player(&updated_enemy, player, walls) {
+    if updated_enemy.charge.is_some() {
+        return charging_boss_hit_player(updated_enemy, player);
+    }
+    if new_attack_delay > 0.0 || updated_enemy.is_staggered() || !can_attack {
         return (updated_enemy, vec![]);
     }
     match enemy.enemy_type {
         EnemyType::Melee | EnemyType::MeleeSlow => melee_enemy_attack_player(updated_enemy),
         EnemyType::Ranged => ranged_enemy_attack_player(updated_enemy, player),
+        EnemyType::Boss => boss_attack_player(updated_enemy, player),
+        EnemyType::Kamikaze => kamikaze_enemy_attack_player(updated_enemy),
+        EnemyType::Summoner => summoner_enemy_attack_player(updated_enemy),
     }
 }
 
@@ -173,7 +244,104 @@ pub fn enemies_attack_player(
         .map(|enemy| enemy_attack_player(player, enemy, walls, delta))
         .unzip();
 
-    (attacked, events.into_iter().flatten().collect())
+    (
+        attacked
+            .into_iter()
+            .filter(|enemy| enemy.hp > 0.0)
+            .collect(),
+        events.into_iter().flatten().collect(),
+    )
+}
+
+/// Returns the vector that moves `entity` out of `other`, or zero if they don't overlap.
+/// Entities at the exact same position are pushed apart along the x axis,
+/// with `tie_breaker` deciding the direction so that both sides don't move the same way.
+fn calculate_push(
+    entity: &Entity,
+    entity_size: f32,
+    other: &Entity,
+    other_size: f32,
+    tie_breaker: bool,
+) -> Vec2 {
+    let offset = entity.position - other.position;
+    let overlap = entity_size + other_size - offset.length();
+    if overlap <= 0.0 {
+        return Vec2::ZERO;
+    }
+
+    let direction = if offset.length_squared() > f32::EPSILON {
+        offset.normalize()
+    } else if tie_breaker {
+        vec2(1.0, 0.0)
+    } else {
+        vec2(-1.0, 0.0)
+    };
+
+    direction * overlap
+}
+
+fn calculate_separation(player: &Player, enemy: &Enemy, enemies: &[Enemy]) -> Vec2 {
+    let enemy_size = enemy.entity.size * ENEMY_COLLISION_SIZE_MODIFIER;
+
+    let from_enemies: Vec2 = enemies
+        .iter()
+        .filter(|other| other.id != enemy.id)
+        .map(|other| {
+            calculate_push(
+                &enemy.entity,
+                enemy_size,
+                &other.entity,
+                other.entity.size * ENEMY_COLLISION_SIZE_MODIFIER,
+                enemy.id < other.id,
+            ) / 2.0
+        })
+        .sum();
+
+    let from_player = calculate_push(
+        &enemy.entity,
+        enemy_size,
+        &player.entity,
+        player.entity.size,
+        true,
+    );
+
+    from_enemies + from_player
+}
+
+fn separate_enemy(player: &Player, enemy: &Enemy, enemies: &[Enemy], walls: &[Wall]) -> Enemy {
+    let separation = calculate_separation(player, enemy, enemies);
+    if separation == Vec2::ZERO {
+        return enemy.clone();
+    }
+
+    // Slides along a wall by keeping whichever part of the push it allows
+    let new_entity = [separation, vec2(separation.x, 0.0), vec2(0.0, separation.y)]
+        .into_iter()
+        .map(|push| Entity {
+            position: enemy.entity.position + push,
+            ..enemy.entity
+        })
+        .find(|entity| get_enemy_intersecting_walls(entity, walls).is_empty());
+
+    match new_entity {
+        Some(entity) => Enemy {
+            entity,
+            ..enemy.clone()
+        },
+        None => enemy.clone(),
+    }
+}
+
+/// Pushes overlapping enemies away from each other and from the player.
+/// Every push is calculated from the positions before the step,
+/// so the result doesn't depend on the order the enemies are processed in.
+pub fn separate_enemies(player: &Player, enemies: Vec<Enemy>, walls: &[Wall]) -> Vec<Enemy> {
+    (0..ENEMY_SEPARATION_ITERATIONS).fold(enemies, |enemies, _| {
+        enemies
+            .par_iter()
+            .map(|enemy| separate_enemy(player, enemy, &enemies, walls))
+            .collect()
+    })
 }
 
 pub fn move_enemies_towards_player(
@@ -182,10 +350,12 @@ pub fn move_enemies_towards_player(
     walls: &[Wall],
     delta: f32,
 ) -> Vec<Enemy> {
-    enemies
+    let moved_enemies = enemies
         .into_par_iter()
-        .map(|enemy| move_enemy_for_type(player, enemy, walls, delta))
-        .collect()
+        .map(|enemy| move_enemy_for_type(player, update_hit_timers(enemy, delta), walls, delta))
+        .collect();
+
+    separate_enemies(player, moved_enemies, walls)
 }
 
 #[cfg(test)]
@@ -222,6 +392,9 @@ mod tests {
             texture: TextureId::Debug,
             start: vec2(-5.0, 0.0),
             end: vec2(5.0, 0.0),
+            is_masked: false,
+            shots_pass_through: false,
+            door: None,
         }];
 
         let delta = 1.0;
@@ -244,4 +417,168 @@ mod tests {
         let not_moved_enemy = move_enemy_for_type(&player, far_enemy.clone(), &walls, delta);
         assert_eq!(not_moved_enemy.entity.position, far_enemy.entity.position);
     }
+
+    #[test]
+    fn test_enemy_can_attack_player_through_fence() {
+        let player = Player {
+            entity: Entity {
+                position: vec2(0.0, 0.0),
+                size: PLAYER_SIZE,
+            },
+            look: vec2(0.0, 0.0),
+        };
+        let enemy = EnemyType::Ranged.to_enemy(vec2(2.0, 0.0), Default::default());
+        let wall = Wall {
+            texture: TextureId::Grate,
+            start: vec2(1.0, -5.0),
+            end: vec2(1.0, 5.0),
+            is_masked: true,
+            shots_pass_through: false,
+            door: None,
+        };
+
+        assert!(!enemy_can_attack_player(
+            &enemy,
+            &player,
+            std::slice::from_ref(&wall)
+        ));
+
+        let fence = Wall {
+            shots_pass_through: true,
+            door: None,
+            ..wall
+        };
+        assert!(enemy_can_attack_player(
+            &enemy,
+            &player,
+            std::slice::from_ref(&fence)
+        ));
+
+        // Melee enemies reach past a thin fence but can't hit through it
+        let melee = EnemyType::Melee.to_enemy(vec2(0.9, 0.0), Default::default());
+        let close_fence = Wall {
+            start: vec2(0.5, -5.0),
+            end: vec2(0.5, 5.0),
+            ..fence
+        };
+        assert!(enemy_can_attack_player(&melee, &player, &[]));
+        assert!(!enemy_can_attack_player(&melee, &player, &[close_fence]));
+    }
+
+    #[test]
+    fn test_kamikaze_attack_kills_itself() {
+        let player = Player {
+            entity: Entity {
+                position: vec2(0.0, 0.0),
+                size: PLAYER_SIZE,
+            },
+            look: vec2(0.0, 0.0),
+        };
+        let kamikaze = Enemy {
+            name: Some("bomber".to_string()),
+            attack_delay: 0.0,
+            ..EnemyType::Kamikaze.to_enemy(vec2(0.5, 0.0), Default::default())
+        };
+
+        let (enemies, events) = enemies_attack_player(&player, vec![kamikaze], &[], 0.1);
+
+        assert!(enemies.is_empty());
+        assert_eq!(
+            events,
+            vec![GameEvent::EnemyKilled {
+                position: vec2(0.5, 0.0),
+                enemy_type: EnemyType::Kamikaze,
+                name: Some("bomber".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_separate_enemies() {
+        let player = Player {
+            entity: Entity {
+                position: vec2(-10.0, 0.0),
+                size: PLAYER_SIZE,
+            },
+            look: vec2(0.0, 0.0),
+        };
+
+        let enemies: Vec<_> = (0..3)
+            .map(|i| Enemy {
+                id: i,
+                entity: Entity {
+                    position: vec2(0.0, 0.0),
+                    size: ENEMY_SIZE,
+                },
+                ..Default::default()
+            })
+            .collect();
+
+        let separated = separate_enemies(&player, enemies.clone(), &[]);
+        let separated_again = separate_enemies(&player, enemies, &[]);
+
+        assert_eq!(separated.len(), 3);
+        assert_ne!(separated[0].entity.position, separated[1].entity.position);
+        for (a, b) in separated.iter().zip(separated_again.iter()) {
+            assert_eq!(a.entity.position, b.entity.position);
+        }
+    }
+
+    #[test]
+    fn test_separate_enemies_from_player() {
+        let player = Player {
+            entity: Entity {
+                position: vec2(0.0, 0.0),
+                size: PLAYER_SIZE,
+            },
+            look: vec2(0.0, 0.0),
+        };
+
+        let enemy = Enemy {
+            entity: Entity {
+                position: vec2(0.1, 0.0),
+                size: ENEMY_SIZE,
+            },
+            ..Default::default()
+        };
+
+        let separated = separate_enemies(&player, vec![enemy.clone()], &[]);
+        let min_distance = PLAYER_SIZE + ENEMY_SIZE * ENEMY_COLLISION_SIZE_MODIFIER;
+
+        assert!(
+            separated[0]
+                .entity
+                .position
+                .distance(player.entity.position)
+                >= min_distance - 0.001
+        );
+
+        let walls = vec![Wall {
+            texture: TextureId::Debug,
+            start: vec2(1.0, -5.0),
+            end: vec2(1.0, 5.0),
+            is_masked: false,
+            shots_pass_through: false,
+            door: None,
+        }];
+        let blocked = separate_enemies(&player, vec![enemy.clone()], &walls);
+        assert_eq!(blocked[0].entity.position, enemy.entity.position);
+
+        // A diagonal push slides along the wall instead of being dropped
+        let enemy = Enemy {
+            entity: Entity {
+                position: vec2(0.1, 0.1),
+                size: ENEMY_SIZE,
+            },
+            ..Default::default()
+        };
+        let walls = vec![Wall {
+            start: vec2(0.91, -5.0),
+            end: vec2(0.91, 5.0),
+            ..walls[0].clone()
+        }];
+        let slid = separate_enemies(&player, vec![enemy.clone()], &walls);
+        assert_eq!(slid[0].entity.position.x, enemy.entity.position.x);
+        assert!(slid[0].entity.position.y > enemy.entity.position.y);
+    }
 }
@@ -0,0 +1,197 @@
+use macroquad::math::Vec2;
+
+use crate::{
+    constants::{
+        ENEMY_HIT_FLASH_TIME, EXPLOSION_ANIMATION_SPEED, EXPLOSION_OFFSET, EXPLOSION_SIZE_MODIFIER,
+    },
+    math::find_intersection,
+    model::{
+        decoration::{Decoration, DecorationGraphics},
+        enemy::Enemy,
+        Animation, Entity, GameEvent, Player, Wall,
+    },
+};
+
+/// Blasts carry through walls that let shots through
+fn is_blocked_by_wall(from: Vec2, to: Vec2, walls: &[Wall]) -> bool {
+    walls
+        .iter()
+        .filter(|wall| !wall.shots_pass_through)
+        .any(|wall| find_intersection(from, to, wall.start, wall.end).is_some())
+}
+
+/// Damage falls off linearly from the center of the blast and walls block it completely.
+pub fn calculate_splash_damage(
+    center: Vec2,
+    radius: f32,
+    damage: f32,
+    target: Vec2,
+    walls: &[Wall],
+) -> f32 {
+    let distance = center.distance(target);
+    if distance >= radius || is_blocked_by_wall(center, target, walls) {
+        return 0.0;
+    }
+
+    damage * (1.0 - distance / radius)
+}
+
+fn damage_enemy(enemy: Enemy, damage: f32) -> Enemy {
+    if damage <= 0.0 {
+        return enemy;
+    }
+
+    Enemy {
+        hp: enemy.hp - damage,
+        hit_timer: ENEMY_HIT_FLASH_TIME,
+        is_engaged: true,
+        ..enemy
+    }
+}
+
+/// Damages the player and every enemy caught in the blast and removes the enemies it kills.
+pub fn resolve_explosion(
+    position: Vec2,
+    radius: f32,
+    damage: f32,
+    player: &Player,
+    enemies: Vec<Enemy>,
+    walls: &[Wall],
+) -> (Vec<Enemy>, Vec<GameEvent>) {
+    let player_damage =
+        calculate_splash_damage(position, radius, damage, player.entity.position, walls);
+    let player_events = (player_damage > 0.0).then_some(GameEvent::PlayerTakeDamage(player_damage));
+
+    let (alive, killed): (Vec<_>, Vec<_>) = enemies
+        .into_iter()
+        .map(|enemy| {
+            let enemy_damage =
+                calculate_splash_damage(position, radius, damage, enemy.entity.position, walls);
+            damage_enemy(enemy, enemy_damage)
+        })
+        .partition(|enemy| enemy.hp > 0.0);
+
+    let events = player_events
+        .into_iter()
+        .chain(killed.into_iter().map(|enemy| GameEvent::EnemyKilled {
+            position: enemy.entity.position,
+            enemy_type: enemy.enemy_type,
+            name: enemy.name,
+        }))
+        .collect();
+
+    (alive, events)
+}
+
+pub fn create_explosion_decoration(position: Vec2, radius: f32) -> Decoration {
+    let animation = Animation::Explosion;
+    let animation_length =
+        (animation.get_textures().len() as u128 * EXPLOSION_ANIMATION_SPEED) as f32 / 1000.0;
+
+    Decoration {
+        entity: Entity {
+            position,
+            size: radius * EXPLOSION_SIZE_MODIFIER,
+        },
+        graphics: DecorationGraphics::Animation {
+            animation,
+            animation_speed: EXPLOSION_ANIMATION_SPEED,
+            looped: false,
+        },
+        life: Some(animation_length),
+        offset: EXPLOSION_OFFSET,
+        age: 0.0,
+        on_expire: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::vec2;
+
+    use crate::{
+        constants::PLAYER_SIZE,
+        model::{enemy::EnemyType, TextureId},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_calculate_splash_damage() {
+        let walls = vec![Wall {
+            texture: TextureId::Debug,
+            start: vec2(-1.0, 1.0),
+            end: vec2(1.0, 1.0),
+            is_masked: false,
+            shots_pass_through: false,
+            door: None,
+        }];
+
+        let center = vec2(0.0, 0.0);
+        assert_eq!(
+            calculate_splash_damage(center, 2.0, 100.0, center, &[]),
+            100.0
+        );
+        assert_eq!(
+            calculate_splash_damage(center, 2.0, 100.0, vec2(1.0, 0.0), &walls),
+            50.0
+        );
+        assert_eq!(
+            calculate_splash_damage(center, 2.0, 100.0, vec2(3.0, 0.0), &[]),
+            0.0
+        );
+        assert_eq!(
+            calculate_splash_damage(center, 2.0, 100.0, vec2(0.0, 1.5), &walls),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_splash_damage_through_fence() {
+        let fence = Wall {
+            texture: TextureId::Grate,
+            start: vec2(-1.0, 1.0),
+            end: vec2(1.0, 1.0),
+            is_masked: true,
+            shots_pass_through: true,
+            door: None,
+        };
+
+        assert_eq!(
+            calculate_splash_damage(vec2(0.0, 0.0), 2.0, 100.0, vec2(0.0, 1.5), &[fence]),
+            25.0
+        );
+    }
+
+    #[test]
+    fn test_resolve_explosion() {
+        let player = Player {
+            entity: Entity {
+                position: vec2(0.0, 1.0),
+                size: PLAYER_SIZE,
+            },
+            look: vec2(0.0, 0.0),
+        };
+        let near = Enemy {
+            hp: 10.0,
+            ..EnemyType::Melee.to_enemy(vec2(0.5, 0.0), Default::default())
+        };
+        let far = EnemyType::Melee.to_enemy(vec2(1.0, 0.0), Default::default());
+        let out_of_range = EnemyType::Melee.to_enemy(vec2(10.0, 0.0), Default::default());
+
+        let (enemies, events) = resolve_explosion(
+            vec2(0.0, 0.0),
+            2.0,
+            100.0,
+            &player,
+            vec![near, far.clone(), out_of_range.clone()],
+            &[],
+        );
+
+        assert_eq!(enemies.len(), 2);
+        assert_eq!(enemies[0].hp, far.hp - 50.0);
+        assert_eq!(enemies[1].hp, out_of_range.hp);
+        assert!(matches!(events[0], GameEvent::PlayerTakeDamage(d) if d == 50.0));
+        assert!(matches!(events[1], GameEvent::EnemyKilled { .. }));
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{
+    constants::HAZARD_TICK_INTERVAL,
+    math::{check_circles_collide, is_point_in_polygon},
+    model::{
+        hazard::{Hazard, HazardSuit},
+        GameEvent, Player, PlayerInfo,
+    },
+};
+
+pub fn find_hazard_at<'a>(hazards: &'a [Hazard], player: &Player) -> Option<&'a Hazard> {
+    hazards
+        .iter()
+        .find(|hazard| is_point_in_polygon(player.entity.position, &hazard.polygon))
+}
+
+/// Damages the player every tick they stand in a hazard without a suit.
+/// The first tick happens as soon as they step in.
+pub fn update_hazards(
+    player: &Player,
+    player_info: PlayerInfo,
+    hazards: &[Hazard],
+    delta: f32,
+) -> (PlayerInfo, Vec<GameEvent>) {
+    let hazard_suit_time = (player_info.hazard_suit_time - delta).max(0.0);
+    let hazard = find_hazard_at(hazards, player).filter(|_| hazard_suit_time <= 0.0);
+
+    let (hazard_tick_time, events) = match hazard {
+        Some(hazard) => {
+            let time = player_info.hazard_tick_time - delta;
+            if time <= 0.0 {
+                (
+                    time + HAZARD_TICK_INTERVAL,
+                    vec![GameEvent::PlayerTakeDamage(hazard.hazard_type.get_damage())],
+                )
+            } else {
+                (time, vec![])
+            }
+        }
+        None => (0.0, vec![]),
+    };
+
+    (
+        PlayerInfo {
+            hazard_suit_time,
+            hazard_tick_time,
+            ..player_info
+        },
+        events,
+    )
+}
+
+pub fn check_pickup_hazard_suit(
+    player: &Player,
+    suits: Vec<HazardSuit>,
+) -> (Vec<HazardSuit>, Vec<GameEvent>) {
+    let (picked_up, remaining): (Vec<_>, Vec<_>) = suits.into_iter().partition(|suit| {
+        check_circles_collide(
+            suit.entity.position,
+            suit.entity.size,
+            player.entity.position,
+            player.entity.size,
+        )
+    });
+
+    let events = picked_up
+        .iter()
+        .map(|_| GameEvent::PickUpHazardSuit)
+        .collect();
+
+    (remaining, events)
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::vec2;
+
+    use crate::model::{hazard::HazardType, Entity};
+
+    use super::*;
+
+    fn create_lava() -> Vec<Hazard> {
+        vec![Hazard {
+            hazard_type: HazardType::Lava,
+            polygon: vec![
+                vec2(0.0, 0.0),
+                vec2(2.0, 0.0),
+                vec2(2.0, 2.0),
+                vec2(0.0, 2.0),
+            ],
+        }]
+    }
+
+    #[test]
+    fn test_update_hazards() {
+        let hazards = create_lava();
+        let player = Player::at(vec2(1.0, 1.0));
+
+        let (info, events) = update_hazards(&player, PlayerInfo::default(), &hazards, 0.1);
+        assert_eq!(events.len(), 1);
+
+        let (info, events) = update_hazards(&player, info, &hazards, 0.1);
+        assert!(events.is_empty());
+
+        let (info, events) = update_hazards(&player, info, &hazards, HAZARD_TICK_INTERVAL);
+        assert_eq!(events.len(), 1);
+
+        let (_, events) = update_hazards(&Player::at(vec2(3.0, 1.0)), info, &hazards, 1.0);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_hazard_suit() {
+        let hazards = create_lava();
+        let player = Player::at(vec2(1.0, 1.0));
+        let suits = vec![HazardSuit {
+            entity: Entity {
+                position: vec2(1.2, 1.0),
+                size: 0.4,
+            },
+        }];
+
+        let (suits, events) = check_pickup_hazard_suit(&player, suits);
+        assert!(suits.is_empty());
+        assert!(matches!(events[..], [GameEvent::PickUpHazardSuit]));
+
+        let info = PlayerInfo {
+            hazard_suit_time: 1.0,
+            ..Default::default()
+        };
+        let (info, events) = update_hazards(&player, info, &hazards, 0.5);
+        assert!(events.is_empty());
+
+        let (_, events) = update_hazards(&player, info, &hazards, 0.6);
+        assert_eq!(events.len(), 1);
+    }
+}
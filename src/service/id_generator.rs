@@ -5,3 +5,8 @@ static ID_COUNTER: AtomicU64 = AtomicU64::new(0);
 pub fn generate_id() -> u64 {
     ID_COUNTER.fetch_add(1, Ordering::SeqCst)
 }
+
+/// Makes sure ids loaded from a save are never generated again
+pub fn reserve_ids_up_to(id: u64) {
+    ID_COUNTER.fetch_max(id + 1, Ordering::SeqCst);
+}
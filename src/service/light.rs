@@ -0,0 +1,82 @@
+use macroquad::math::{Vec2, Vec3};
+
+use crate::{
+    math::find_intersection,
+    model::{light::Light, Wall},
+};
+
+fn is_lit_by(light: &Light, point: Vec2, walls: &[Wall]) -> bool {
+    point.distance(light.position) < light.radius
+        && !walls
+            .iter()
+            .filter(|wall| !wall.is_masked)
+            .any(|wall| find_intersection(light.position, point, wall.start, wall.end).is_some())
+}
+
+fn get_contribution(light: &Light, point: Vec2) -> Vec3 {
+    let falloff = 1.0 - point.distance(light.position) / light.radius;
+    light.color * light.get_current_intensity() * falloff * falloff
+}
+
+/// Sum of the colours of all the lights that reach `point` without a wall in the way
+pub fn calculate_light_at(point: Vec2, lights: &[Light], walls: &[Wall]) -> Vec3 {
+    lights
+        .iter()
+        .filter(|light| is_lit_by(light, point, walls))
+        .map(|light| get_contribution(light, point))
+        .sum()
+}
+
+/// Fades temporary lights and removes the ones that went out
+pub fn update_lights(lights: Vec<Light>, delta: f32) -> Vec<Light> {
+    lights
+        .into_iter()
+        .filter_map(|light| light.update(delta))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::{vec2, vec3};
+
+    use crate::model::TextureId;
+
+    use super::*;
+
+    #[test]
+    fn test_calculate_light_at() {
+        let red = Light::new(vec2(0.0, 0.0), vec3(1.0, 0.0, 0.0), 4.0, 1.0);
+        let blue = Light::new(vec2(4.0, 0.0), vec3(0.0, 0.0, 1.0), 4.0, 1.0);
+        let lights = [red, blue];
+
+        let light = calculate_light_at(vec2(2.0, 0.0), &lights, &[]);
+        assert!((light - vec3(0.25, 0.0, 0.25)).length() < 0.0001);
+
+        let out_of_range = calculate_light_at(vec2(0.0, 10.0), &lights, &[]);
+        assert_eq!(out_of_range, Vec3::ZERO);
+
+        let wall = Wall {
+            start: vec2(1.0, -1.0),
+            end: vec2(1.0, 1.0),
+            texture: TextureId::Stone,
+            is_masked: false,
+            shots_pass_through: false,
+            door: None,
+        };
+        let blocked = calculate_light_at(vec2(2.0, 0.0), &lights, &[wall]);
+        assert!((blocked - vec3(0.0, 0.0, 0.25)).length() < 0.0001);
+    }
+
+    #[test]
+    fn test_update_lights() {
+        let fixed = Light::new(vec2(0.0, 0.0), Vec3::ONE, 1.0, 1.0);
+        let flash = Light::temporary(vec2(0.0, 0.0), Vec3::ONE, 1.0, 1.0, 0.2);
+
+        let lights = update_lights(vec![fixed, flash], 0.1);
+        assert_eq!(lights.len(), 2);
+        assert!((lights[1].get_current_intensity() - 0.5).abs() < 0.0001);
+
+        let lights = update_lights(lights, 0.1);
+        assert_eq!(lights, vec![fixed]);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{
+    math::check_circles_collide,
+    model::{objective::Objective, GameObjects},
+};
+
+use super::player::is_player_at_exit;
+
+pub fn is_objective_complete(objective: &Objective, game_objects: &GameObjects) -> bool {
+    match objective {
+        Objective::CollectAllKeys => game_objects.keys.is_empty(),
+        Objective::ReachExit => is_player_at_exit(game_objects),
+        // Spawners without a limit never run out, only the finite ones have to be used up
+        Objective::KillAllEnemies => {
+            game_objects.enemies.is_empty()
+                && game_objects
+                    .spawners
+                    .iter()
+                    .all(|spawner| spawner.remaining.is_none_or(|remaining| remaining == 0))
+        }
+        Objective::KillTarget(name) => game_objects.killed_targets.contains(name),
+        Objective::Survive(seconds) => game_objects.level_time >= *seconds,
+        Objective::ReachLocation { position, radius } => check_circles_collide(
+            *position,
+            *radius,
+            game_objects.player.entity.position,
+            game_objects.player.entity.size,
+        ),
+        Objective::CollectKeys(count) => game_objects.player_info.picked_up_keys >= *count,
+        Objective::AllOf(objectives) => objectives
+            .iter()
+            .all(|objective| is_objective_complete(objective, game_objects)),
+        Objective::AnyOf(objectives) => objectives
+            .iter()
+            .any(|objective| is_objective_complete(objective, game_objects)),
+    }
+}
+
+pub fn are_objectives_complete(game_objects: &GameObjects) -> bool {
+    game_objects
+        .objectives
+        .iter()
+        .all(|objective| is_objective_complete(objective, game_objects))
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::vec2;
+
+    use crate::model::{
+        enemy::{Enemy, EnemyType},
+        key_object::KeyObject,
+        spawner::{SpawnTrigger, Spawner},
+        Entity,
+    };
+
+    use super::*;
+
+    fn create_game_objects() -> GameObjects {
+        GameObjects {
+            enemies: vec![Enemy {
+                name: Some("Guard".to_string()),
+                ..EnemyType::Melee.to_enemy(vec2(5.0, 5.0), Default::default())
+            }],
+            keys: vec![KeyObject {
+                entity: Entity {
+                    position: vec2(3.0, 3.0),
+                    size: 0.1,
+                },
+            }],
+            level_time: 10.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_is_objective_complete() {
+        let game_objects = create_game_objects();
+
+        assert!(!is_objective_complete(
+            &Objective::CollectAllKeys,
+            &game_objects
+        ));
+        assert!(!is_objective_complete(
+            &Objective::KillTarget("Guard".to_string()),
+            &game_objects
+        ));
+        // Targets that never spawned don't count as killed
+        assert!(!is_objective_complete(
+            &Objective::KillTarget("Boss".to_string()),
+            &game_objects
+        ));
+        assert!(is_objective_complete(
+            &Objective::KillTarget("Guard".to_string()),
+            &GameObjects {
+                killed_targets: vec!["Guard".to_string()],
+                ..create_game_objects()
+            }
+        ));
+        assert!(is_objective_complete(
+            &Objective::Survive(5.0),
+            &game_objects
+        ));
+        assert!(!is_objective_complete(
+            &Objective::Survive(15.0),
+            &game_objects
+        ));
+        assert!(is_objective_complete(
+            &Objective::ReachLocation {
+                position: vec2(0.5, 0.0),
+                radius: 1.0
+            },
+            &game_objects
+        ));
+        assert!(is_objective_complete(
+            &Objective::AnyOf(vec![Objective::KillAllEnemies, Objective::Survive(5.0)]),
+            &game_objects
+        ));
+        assert!(!is_objective_complete(
+            &Objective::AllOf(vec![Objective::KillAllEnemies, Objective::Survive(5.0)]),
+            &game_objects
+        ));
+    }
+
+    #[test]
+    fn test_kill_all_enemies_with_spawners() {
+        let spawner = Spawner {
+            position: vec2(5.0, 0.0),
+            enemy_type: EnemyType::Melee,
+            trigger: SpawnTrigger::PlayerInRange(3.0),
+            is_triggered: false,
+            interval: 1.0,
+            time_until_spawn: 0.0,
+            max_alive: 1,
+            remaining: Some(2),
+            spawned_ids: vec![],
+        };
+        let game_objects = |remaining| GameObjects {
+            spawners: vec![Spawner {
+                remaining,
+                ..spawner.clone()
+            }],
+            ..Default::default()
+        };
+
+        assert!(!is_objective_complete(
+            &Objective::KillAllEnemies,
+            &game_objects(Some(2))
+        ));
+        assert!(is_objective_complete(
+            &Objective::KillAllEnemies,
+            &game_objects(Some(0))
+        ));
+        assert!(is_objective_complete(
+            &Objective::KillAllEnemies,
+            &game_objects(None)
+        ));
+    }
+
+    #[test]
+    fn test_are_objectives_complete() {
+        let game_objects = GameObjects {
+            objectives: vec![Objective::Survive(5.0), Objective::CollectKeys(0)],
+            ..create_game_objects()
+        };
+        assert!(are_objectives_complete(&game_objects));
+
+        let game_objects = GameObjects {
+            objectives: vec![Objective::Survive(5.0), Objective::CollectKeys(1)],
+            ..game_objects
+        };
+        assert!(!are_objectives_complete(&game_objects));
+    }
+}
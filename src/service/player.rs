@@ -2,9 +2,13 @@ use macroquad::math::{vec2, Vec2};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 use crate::{
-    constants::{MOVE_SPEED, PLAYER_MAX_HEALTH, PLAYER_REGENERATION},
+    console::Cvars,
+    constants::{
+        PLAYER_MAX_HEALTH, PLAYER_MAX_STAMINA, PLAYER_REGENERATION, STAMINA_DRAIN,
+        STAMINA_EXHAUSTED_THRESHOLD, STAMINA_REGENERATION,
+    },
     math::{check_circles_collide, line_intersects_circle, rotate_point},
-    model::{Entity, GameObjects, Player, Wall},
+    model::{difficulty::Difficulty, Entity, GameObjects, Player, PlayerInfo, Wall},
 };
 
 pub fn move_player_entity(player_entity: Entity, movement: Vec2, walls: &[Wall]) -> Entity {
@@ -25,17 +29,14 @@ pub fn move_player_entity(player_entity: Entity, movement: Vec2, walls: &[Wall])
 }
 
 pub fn move_player(
-    game_objects: &GameObjects,
+    walls: &[Wall],
     player: Player,
     direction: Vec2,
+    speed: f32,
     delta: f32,
 ) -> Player {
     Player {
-        entity: move_player_entity(
-            player.entity,
-            direction * delta * MOVE_SPEED,
-            &game_objects.walls,
-        ),
+        entity: move_player_entity(player.entity, direction * delta * speed, walls),
         ..player
     }
 }
@@ -56,8 +57,45 @@ pub fn is_player_at_exit(game_objects: &GameObjects) -> bool {
     })
 }
 
-pub fn regenerate_health(player_health: f32, delta: f32) -> f32 {
-    (player_health + delta * PLAYER_REGENERATION).min(PLAYER_MAX_HEALTH)
+pub fn regenerate_health(player_health: f32, difficulty: Difficulty, delta: f32) -> f32 {
+    let regeneration = PLAYER_REGENERATION * difficulty.get_player_regeneration_modifier();
+    (player_health + delta * regeneration).min(PLAYER_MAX_HEALTH)
+}
+
+pub fn can_sprint(player_info: &PlayerInfo) -> bool {
+    !player_info.is_exhausted && player_info.stamina > 0.0
+}
+
+pub fn get_move_speed(is_sprinting: bool, cvars: &Cvars) -> f32 {
+    if is_sprinting {
+        cvars.move_speed * cvars.sprint_speed_modifier
+    } else {
+        cvars.move_speed
+    }
+}
+
+/// Drains stamina while sprinting and regenerates it otherwise.
+/// Running out of stamina exhausts the player until it regenerates past a threshold.
+pub fn update_stamina(player_info: PlayerInfo, is_sprinting: bool, delta: f32) -> PlayerInfo {
+    let stamina = if is_sprinting {
+        (player_info.stamina - delta * STAMINA_DRAIN).max(0.0)
+    } else {
+        (player_info.stamina + delta * STAMINA_REGENERATION).min(PLAYER_MAX_STAMINA)
+    };
+
+    let is_exhausted = if stamina <= 0.0 {
+        true
+    } else if stamina >= STAMINA_EXHAUSTED_THRESHOLD {
+        false
+    } else {
+        player_info.is_exhausted
+    };
+
+    PlayerInfo {
+        stamina,
+        is_exhausted,
+        ..player_info
+    }
 }
 
 #[cfg(test)]
@@ -79,6 +117,9 @@ mod tests {
             texture: TextureId::Debug,
             start: vec2(-10.0, 1.5),
             end: vec2(10.0, 1.5),
+            is_masked: false,
+            shots_pass_through: false,
+            door: None,
         }];
 
         let moved1 = move_player_entity(entity, movement1, &walls);
@@ -89,4 +130,29 @@ mod tests {
 
         assert!(moved2.position.y > entity.position.y);
     }
+
+    #[test]
+    fn test_update_stamina() {
+        let player_info = PlayerInfo {
+            stamina: 10.0,
+            ..Default::default()
+        };
+
+        let drained = update_stamina(player_info.clone(), true, 10.0 / STAMINA_DRAIN);
+        assert_eq!(drained.stamina, 0.0);
+        assert!(drained.is_exhausted);
+        assert!(!can_sprint(&drained));
+
+        let recovering = update_stamina(drained, false, 1.0);
+        assert!(recovering.stamina > 0.0);
+        assert_eq!(
+            recovering.is_exhausted,
+            recovering.stamina < STAMINA_EXHAUSTED_THRESHOLD
+        );
+
+        let recovered = update_stamina(recovering, false, 100.0);
+        assert_eq!(recovered.stamina, PLAYER_MAX_STAMINA);
+        assert!(!recovered.is_exhausted);
+        assert!(can_sprint(&recovered));
+    }
 }
@@ -13,6 +13,7 @@ fn find_projectile_hit_walls(
 ) -> Option<Vec2> {
     walls
         .iter()
+        .filter(|wall| !wall.shots_pass_through)
         .filter_map(|wall| find_intersection(wall.start, wall.end, old_position, new_position))
         .next()
 }
@@ -163,6 +164,9 @@ mod tests {
             texture: TextureId::default(),
             start: vec2(-10.0, 5.0),
             end: vec2(10.0, 5.0),
+            is_masked: false,
+            shots_pass_through: false,
+            door: None,
         }];
 
         let delta = 1.0;
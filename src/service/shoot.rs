@@ -2,15 +2,15 @@ use macroquad::math::Vec2;
 
 use crate::{
     constants::{
-        CORPSE_OFFSET, CORPSE_SIZE, CREATE_GUNSHOT_HIT_ANIMATION_OFFSET_TO_CAMERA,
-        GUNSHOT_ANIMATION_LENGTH, GUNSHOT_ANIMATION_SPEED, GUN_DAMAGE, MAX_BULLETS,
-        MAX_SHOOT_DISTANCE, RELOAD_SPEED, SHOOT_SPEED,
+        CORPSE_OFFSET, CREATE_GUNSHOT_HIT_ANIMATION_OFFSET_TO_CAMERA, ENEMY_DEATH_ANIMATION_SPEED,
+        ENEMY_HIT_FLASH_TIME, ENEMY_SIZE, GUNSHOT_ANIMATION_LENGTH, GUNSHOT_ANIMATION_SPEED,
+        GUN_DAMAGE, MAX_SHOOT_DISTANCE, RELOAD_SPEED, SHOOT_SPEED,
     },
     math::{find_intersection, line_intersects_circle},
     model::{
         decoration::{Decoration, DecorationGraphics},
-        enemy::Enemy,
-        Animation, Entity, GameEvent, Player, PlayerInfo, ShootingStatus, TextureId, Wall,
+        enemy::{Enemy, EnemyType},
+        Animation, Entity, GameEvent, Player, PlayerInfo, ShootingStatus, Wall,
     },
 };
 
@@ -66,7 +66,7 @@ fn update_shoot_not_shooting(
         (
             PlayerInfo {
                 time_since_last_shot,
-                bullets: MAX_BULLETS,
+                bullets: player_info.max_bullets,
                 ..player_info
             },
             false,
@@ -91,7 +91,7 @@ fn update_shoot_reloading(
             PlayerInfo {
                 shooting_status: ShootingStatus::NotShooting,
                 time_since_last_shot,
-                bullets: MAX_BULLETS,
+                bullets: player_info.max_bullets,
                 ..player_info
             },
             false,
@@ -126,6 +126,7 @@ fn find_shot_enemy<'a>(
 
     let closest_hit_wall = walls
         .iter()
+        .filter(|wall| !wall.shots_pass_through)
         .filter_map(|wall| {
             find_intersection(player.entity.position, shoot_ray, wall.start, wall.end)
         })
@@ -173,6 +174,40 @@ fn create_shot_particles_event(shot_location: Vec2) -> GameEvent {
     }
 }
 
+fn knock_back_enemy(player: &Player, enemy: &Enemy, walls: &[Wall]) -> Entity {
+    let direction = (enemy.entity.position - player.entity.position).normalize_or_zero();
+    let knocked_back = Entity {
+        position: enemy.entity.position + direction * enemy.enemy_type.get_knockback_distance(),
+        ..enemy.entity
+    };
+
+    let hits_wall = walls.iter().any(|wall| {
+        line_intersects_circle(
+            wall.start,
+            wall.end,
+            knocked_back.position,
+            knocked_back.size,
+        )
+    });
+
+    if hits_wall {
+        enemy.entity
+    } else {
+        knocked_back
+    }
+}
+
+fn hit_enemy(player: &Player, enemy: Enemy, walls: &[Wall]) -> Enemy {
+    Enemy {
+        hp: enemy.hp - GUN_DAMAGE,
+        hit_timer: ENEMY_HIT_FLASH_TIME,
+        stagger_timer: enemy.enemy_type.get_stagger_time(),
+        entity: knock_back_enemy(player, &enemy, walls),
+        is_engaged: true,
+        ..enemy
+    }
+}
+
 pub fn shoot_enemies(
     player: &Player,
     mut enemies: Vec<Enemy>,
@@ -180,26 +215,22 @@ pub fn shoot_enemies(
 ) -> (Vec<Enemy>, Vec<GameEvent>) {
     let (shot_enemy_option, shot_location) = find_shot_enemy(player, &enemies, walls);
 
-    let shot_event = if let Some(some) = shot_location {
-        vec![create_shot_particles_event(some)]
-    } else {
-        vec![]
-    };
+    let shot_event: Vec<_> = shot_location
+        .map(create_shot_particles_event)
+        .into_iter()
+        .chain(std::iter::once(GameEvent::ShotFired {
+            hit: shot_enemy_option.is_some(),
+        }))
+        .collect();
 
     if shot_enemy_option.is_none() {
         return (enemies, shot_event);
     }
     let shot_enemy_id = shot_enemy_option.unwrap().id;
 
-    enemies
-        .iter_mut()
-        .find(|enemy| enemy.id == shot_enemy_id)
-        .map(|enemy| {
-            *enemy = Enemy {
-                hp: enemy.hp - GUN_DAMAGE,
-                ..*enemy
-            };
-        });
+    if let Some(enemy) = enemies.iter_mut().find(|enemy| enemy.id == shot_enemy_id) {
+        *enemy = hit_enemy(player, enemy.clone(), walls);
+    }
 
     let game_events = enemies
         .iter()
@@ -207,6 +238,8 @@ pub fn shoot_enemies(
             if enemy.hp <= 0.0 {
                 Some(GameEvent::EnemyKilled {
                     position: enemy.entity.position,
+                    enemy_type: enemy.enemy_type,
+                    name: enemy.name.clone(),
                 })
             } else {
                 None
@@ -220,15 +253,40 @@ pub fn shoot_enemies(
     (enemies, game_events)
 }
 
-pub fn create_corpse(location: Vec2) -> Decoration {
+pub fn create_corpse(location: Vec2, enemy_type: EnemyType) -> Decoration {
     Decoration {
         entity: Entity {
             position: location,
-            size: CORPSE_SIZE,
+            size: ENEMY_SIZE,
         },
-        graphics: DecorationGraphics::Texture(TextureId::Skull),
+        graphics: DecorationGraphics::Texture(enemy_type.get_corpse_texture()),
         life: None,
         offset: CORPSE_OFFSET,
+        age: 0.0,
+        on_expire: None,
+    }
+}
+
+/// Plays the enemy type's death animation and leaves its corpse once it finishes
+pub fn create_death_animation(location: Vec2, enemy_type: EnemyType) -> Decoration {
+    let animation = enemy_type.get_death_animation();
+    let animation_length =
+        (animation.get_textures().len() as u128 * ENEMY_DEATH_ANIMATION_SPEED) as f32 / 1000.0;
+
+    Decoration {
+        entity: Entity {
+            position: location,
+            size: ENEMY_SIZE,
+        },
+        graphics: DecorationGraphics::Animation {
+            animation,
+            animation_speed: ENEMY_DEATH_ANIMATION_SPEED,
+            looped: false,
+        },
+        life: Some(animation_length),
+        offset: CORPSE_OFFSET,
+        age: 0.0,
+        on_expire: Some(Box::new(create_corpse(location, enemy_type))),
     }
 }
 
@@ -267,9 +325,12 @@ pub fn create_shot_animation_decoration(player: &Player, location: Vec2) -> Deco
         graphics: DecorationGraphics::Animation {
             animation: Animation::Explosion,
             animation_speed: GUNSHOT_ANIMATION_SPEED,
+            looped: true,
         },
         life: Some(GUNSHOT_ANIMATION_LENGTH),
         offset: 0.1,
+        age: 0.0,
+        on_expire: None,
     }
 }
 
@@ -277,7 +338,10 @@ pub fn create_shot_animation_decoration(player: &Player, location: Vec2) -> Deco
 mod tests {
     use macroquad::math::vec2;
 
-    use crate::constants::{ENEMY_SIZE, PLAYER_SIZE};
+    use crate::{
+        constants::{MAX_BULLETS, PLAYER_SIZE},
+        model::TextureId,
+    };
 
     use super::*;
 
@@ -305,8 +369,45 @@ mod tests {
         let (remaining_enemies, game_events) = shoot_enemies(&player, vec![enemy.clone()], &walls);
 
         assert_eq!(remaining_enemies[0].hp, 100.0 - GUN_DAMAGE);
+        assert!(remaining_enemies[0].hit_timer > 0.0);
+        assert!(remaining_enemies[0].is_staggered());
+        assert!(remaining_enemies[0].entity.position.x > enemy.entity.position.x);
 
         assert!(matches!(game_events[0], GameEvent::LocationShot { .. }));
+        assert!(matches!(game_events[1], GameEvent::ShotFired { hit: true }));
+
+        let (_, game_events) = shoot_enemies(
+            &Player {
+                look: vec2(-1.0, 0.0),
+                ..player
+            },
+            vec![enemy],
+            &walls,
+        );
+        assert!(matches!(
+            game_events[..],
+            [GameEvent::ShotFired { hit: false }]
+        ));
+    }
+
+    #[test]
+    fn test_death_animation_leaves_corpse() {
+        let decoration = create_death_animation(vec2(1.0, 2.0), EnemyType::Ranged);
+        let life = decoration.life.unwrap();
+
+        let playing = decoration.update(life / 2.0).unwrap();
+        assert!(matches!(
+            playing.graphics,
+            DecorationGraphics::Animation { looped: false, .. }
+        ));
+
+        let corpse = playing.update(life).unwrap();
+        assert!(matches!(
+            corpse.graphics,
+            DecorationGraphics::Texture(TextureId::RangedEnemyCorpse)
+        ));
+        assert!(corpse.life.is_none());
+        assert_eq!(corpse.entity.position, vec2(1.0, 2.0));
     }
 
     #[test]
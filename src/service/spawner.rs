@@ -0,0 +1,196 @@
+use std::f32::consts::TAU;
+
+use macroquad::math::{vec2, Vec2};
+
+use crate::{
+    math::rotate_point,
+    model::{
+        difficulty::Difficulty,
+        enemy::{Enemy, EnemyType},
+        spawner::{SpawnTrigger, Spawner},
+        GameEvent, Player,
+    },
+};
+
+/// Creates `count` spawn events evenly spaced on a circle around `position`.
+pub fn create_summon_events(
+    position: Vec2,
+    enemy_type: EnemyType,
+    count: usize,
+    distance: f32,
+) -> Vec<GameEvent> {
+    (0..count)
+        .map(|i| {
+            let angle = i as f32 * TAU / count as f32;
+            GameEvent::SpawnEnemy {
+                position: position + rotate_point(vec2(distance, 0.0), Vec2::ZERO, angle),
+                enemy_type,
+            }
+        })
+        .collect()
+}
+
+fn is_trigger_met(trigger: SpawnTrigger, position: Vec2, player: &Player, keys: usize) -> bool {
+    match trigger {
+        SpawnTrigger::Always => true,
+        SpawnTrigger::PlayerInRange(range) => player.entity.position.distance(position) <= range,
+        SpawnTrigger::KeysPickedUp(required) => keys >= required,
+    }
+}
+
+fn update_spawner(
+    spawner: Spawner,
+    player: &Player,
+    picked_up_keys: usize,
+    enemies: &[Enemy],
+    difficulty: Difficulty,
+    delta: f32,
+) -> (Spawner, Option<Enemy>) {
+    let spawned_ids: Vec<_> = spawner
+        .spawned_ids
+        .into_iter()
+        .filter(|id| enemies.iter().any(|enemy| enemy.id == *id))
+        .collect();
+    let is_triggered = spawner.is_triggered
+        || is_trigger_met(spawner.trigger, spawner.position, player, picked_up_keys);
+
+    if !is_triggered {
+        return (
+            Spawner {
+                spawned_ids,
+                ..spawner
+            },
+            None,
+        );
+    }
+
+    let time_until_spawn = (spawner.time_until_spawn - delta).max(0.0);
+    if time_until_spawn > 0.0
+        || spawned_ids.len() >= spawner.max_alive
+        || spawner.remaining == Some(0)
+    {
+        return (
+            Spawner {
+                spawned_ids,
+                is_triggered,
+                time_until_spawn,
+                ..spawner
+            },
+            None,
+        );
+    }
+
+    let enemy = spawner.enemy_type.to_enemy(spawner.position, difficulty);
+    let spawned_ids = spawned_ids.into_iter().chain(Some(enemy.id)).collect();
+
+    (
+        Spawner {
+            spawned_ids,
+            is_triggered,
+            time_until_spawn: spawner.interval,
+            remaining: spawner.remaining.map(|remaining| remaining - 1),
+            ..spawner
+        },
+        Some(enemy),
+    )
+}
+
+/// Advances the spawn timers and returns the enemies created this step.
+pub fn update_spawners(
+    spawners: Vec<Spawner>,
+    player: &Player,
+    picked_up_keys: usize,
+    enemies: &[Enemy],
+    difficulty: Difficulty,
+    delta: f32,
+) -> (Vec<Spawner>, Vec<Enemy>) {
+    let (spawners, new_enemies): (Vec<_>, Vec<_>) = spawners
+        .into_iter()
+        .map(|spawner| update_spawner(spawner, player, picked_up_keys, enemies, difficulty, delta))
+        .unzip();
+
+    (spawners, new_enemies.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constants::PLAYER_SIZE, model::Entity};
+
+    use super::*;
+
+    #[test]
+    fn test_update_spawners() {
+        let player = Player {
+            entity: Entity {
+                position: vec2(0.0, 0.0),
+                size: PLAYER_SIZE,
+            },
+            look: vec2(0.0, 0.0),
+        };
+        let spawner = Spawner {
+            position: vec2(5.0, 0.0),
+            enemy_type: EnemyType::Melee,
+            trigger: SpawnTrigger::PlayerInRange(3.0),
+            is_triggered: false,
+            interval: 1.0,
+            time_until_spawn: 0.0,
+            max_alive: 1,
+            remaining: Some(2),
+            spawned_ids: vec![],
+        };
+
+        let (spawners, enemies) = update_spawners(
+            vec![spawner.clone()],
+            &player,
+            0,
+            &[],
+            Difficulty::Normal,
+            0.1,
+        );
+        assert!(enemies.is_empty());
+        assert!(!spawners[0].is_triggered);
+
+        let close_player = Player {
+            entity: Entity {
+                position: vec2(3.0, 0.0),
+                ..player.entity
+            },
+            ..player
+        };
+        let (spawners, enemies) = update_spawners(
+            vec![spawner],
+            &close_player,
+            0,
+            &[],
+            Difficulty::Normal,
+            0.1,
+        );
+        assert_eq!(enemies.len(), 1);
+        assert_eq!(spawners[0].remaining, Some(1));
+
+        let (capped, none) = update_spawners(
+            spawners.clone(),
+            &player,
+            0,
+            &enemies,
+            Difficulty::Normal,
+            2.0,
+        );
+        assert!(none.is_empty());
+        assert_eq!(capped[0].spawned_ids.len(), 1);
+
+        let (_, respawned) = update_spawners(spawners, &player, 0, &[], Difficulty::Normal, 2.0);
+        assert_eq!(respawned.len(), 1);
+    }
+
+    #[test]
+    fn test_create_summon_events() {
+        let events = create_summon_events(vec2(1.0, 1.0), EnemyType::Melee, 4, 2.0);
+
+        assert_eq!(events.len(), 4);
+        assert!(events.iter().all(|event| matches!(
+            event,
+            GameEvent::SpawnEnemy { position, .. } if (position.distance(vec2(1.0, 1.0)) - 2.0).abs() < 0.001
+        )));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{
+    math::is_point_in_polygon,
+    model::{level_stats::LevelStats, secret::Secret, GameEvent, GameObjects, Player},
+};
+
+pub fn update_secrets(player: &Player, secrets: Vec<Secret>) -> (Vec<Secret>, Vec<GameEvent>) {
+    let (secrets, events): (Vec<_>, Vec<_>) = secrets
+        .into_iter()
+        .map(|secret| {
+            if secret.is_found || !is_point_in_polygon(player.entity.position, &secret.polygon) {
+                return (secret, None);
+            }
+
+            (
+                Secret {
+                    is_found: true,
+                    ..secret
+                },
+                Some(GameEvent::SecretFound),
+            )
+        })
+        .unzip();
+
+    (secrets, events.into_iter().flatten().collect())
+}
+
+/// Stats with the totals filled in from the final state of the level
+pub fn finish_level_stats(game_objects: &GameObjects) -> LevelStats {
+    LevelStats {
+        total_enemies: game_objects.stats.kills + game_objects.enemies.len(),
+        total_secrets: game_objects.secrets.len(),
+        ..game_objects.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::vec2;
+
+    use crate::model::enemy::Enemy;
+
+    use super::*;
+
+    #[test]
+    fn test_update_secrets() {
+        let secrets = vec![Secret {
+            polygon: vec![
+                vec2(0.0, 0.0),
+                vec2(1.0, 0.0),
+                vec2(1.0, 1.0),
+                vec2(0.0, 1.0),
+            ],
+            is_found: false,
+        }];
+
+        let (secrets, events) = update_secrets(&Player::at(vec2(2.0, 0.5)), secrets);
+        assert!(events.is_empty());
+
+        let (secrets, events) = update_secrets(&Player::at(vec2(0.5, 0.5)), secrets);
+        assert!(matches!(events[..], [GameEvent::SecretFound]));
+        assert!(secrets[0].is_found);
+
+        let (_, events) = update_secrets(&Player::at(vec2(0.5, 0.5)), secrets);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_finish_level_stats() {
+        let game_objects = GameObjects {
+            enemies: vec![Enemy::default(), Enemy::default()],
+            secrets: vec![Secret {
+                polygon: vec![],
+                is_found: false,
+            }],
+            stats: LevelStats {
+                kills: 3,
+                shots_fired: 4,
+                shots_hit: 3,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let stats = finish_level_stats(&game_objects);
+
+        assert_eq!(stats.total_enemies, 5);
+        assert_eq!(stats.total_secrets, 1);
+        assert_eq!(stats.get_accuracy(), 75.0);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{
+    constants::{
+        SURVIVAL_BASE_WAVE_SIZE, SURVIVAL_BOSS_WAVE_INTERVAL, SURVIVAL_WAVE_DELAY,
+        SURVIVAL_WAVE_SIZE_INCREASE,
+    },
+    model::{
+        difficulty::Difficulty,
+        enemy::{Enemy, EnemyType},
+        survival::SurvivalState,
+    },
+};
+
+/// Enemy types that can appear in a wave, tougher types unlock in later waves
+fn get_wave_enemy_types(wave: u32) -> Vec<EnemyType> {
+    [
+        (1, EnemyType::Melee),
+        (2, EnemyType::Ranged),
+        (3, EnemyType::Kamikaze),
+        (4, EnemyType::MeleeSlow),
+        (6, EnemyType::Summoner),
+    ]
+    .into_iter()
+    .filter(|(unlocked_at, _)| wave >= *unlocked_at)
+    .map(|(_, enemy_type)| enemy_type)
+    .collect()
+}
+
+pub fn create_wave(survival: &SurvivalState, wave: u32, difficulty: Difficulty) -> Vec<Enemy> {
+    if survival.spawn_points.is_empty() {
+        return vec![];
+    }
+
+    let enemy_types = get_wave_enemy_types(wave);
+    let size = SURVIVAL_BASE_WAVE_SIZE + (wave - 1) * SURVIVAL_WAVE_SIZE_INCREASE;
+    let boss = (wave % SURVIVAL_BOSS_WAVE_INTERVAL == 0).then_some(EnemyType::Boss);
+
+    (0..size as usize)
+        .map(|i| enemy_types[i % enemy_types.len()])
+        .chain(boss)
+        .enumerate()
+        .map(|(i, enemy_type)| {
+            let position = survival.spawn_points[i % survival.spawn_points.len()];
+            Enemy {
+                is_engaged: true,
+                ..enemy_type.to_enemy(position, difficulty)
+            }
+        })
+        .collect()
+}
+
+/// Counts the survival time and spawns the next wave once the previous one is cleared.
+pub fn update_survival(
+    survival: SurvivalState,
+    enemies: &[Enemy],
+    difficulty: Difficulty,
+    delta: f32,
+) -> (SurvivalState, Vec<Enemy>) {
+    let time_survived = survival.time_survived + delta;
+    if !enemies.is_empty() {
+        return (
+            SurvivalState {
+                time_survived,
+                ..survival
+            },
+            vec![],
+        );
+    }
+
+    let time_until_next_wave = survival.time_until_next_wave - delta;
+    if time_until_next_wave > 0.0 {
+        return (
+            SurvivalState {
+                time_survived,
+                time_until_next_wave,
+                ..survival
+            },
+            vec![],
+        );
+    }
+
+    let wave = survival.wave + 1;
+    let wave_enemies = create_wave(&survival, wave, difficulty);
+    (
+        SurvivalState {
+            wave,
+            time_survived,
+            time_until_next_wave: SURVIVAL_WAVE_DELAY,
+            ..survival
+        },
+        wave_enemies,
+    )
+}
+
+pub fn add_kill_score(survival: SurvivalState, enemy_type: EnemyType) -> SurvivalState {
+    SurvivalState {
+        kill_score: survival.kill_score + enemy_type.get_score(),
+        ..survival
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::vec2;
+
+    use crate::constants::{SURVIVAL_FIRST_WAVE_DELAY, SURVIVAL_SCORE_PER_SECOND};
+
+    use super::*;
+
+    #[test]
+    fn test_update_survival() {
+        let survival = SurvivalState::new(vec![vec2(0.0, 0.0), vec2(5.0, 5.0)]);
+
+        let (waiting, enemies) = update_survival(survival, &[], Difficulty::Normal, 1.0);
+        assert!(enemies.is_empty());
+        assert_eq!(waiting.wave, 0);
+
+        let (first_wave, enemies) =
+            update_survival(waiting, &[], Difficulty::Normal, SURVIVAL_FIRST_WAVE_DELAY);
+        assert_eq!(first_wave.wave, 1);
+        assert_eq!(enemies.len(), SURVIVAL_BASE_WAVE_SIZE as usize);
+
+        let (fighting, no_enemies) =
+            update_survival(first_wave, &enemies, Difficulty::Normal, 10.0);
+        assert!(no_enemies.is_empty());
+        assert_eq!(fighting.time_until_next_wave, SURVIVAL_WAVE_DELAY);
+
+        let scored = add_kill_score(fighting, EnemyType::Melee);
+        assert_eq!(
+            scored.get_score(),
+            EnemyType::Melee.get_score()
+                + (1.0 + SURVIVAL_FIRST_WAVE_DELAY + 10.0) as u32 * SURVIVAL_SCORE_PER_SECOND
+        );
+    }
+
+    #[test]
+    fn test_create_wave_adds_boss() {
+        let survival = SurvivalState::new(vec![vec2(0.0, 0.0)]);
+
+        let wave = create_wave(&survival, SURVIVAL_BOSS_WAVE_INTERVAL, Difficulty::Normal);
+
+        assert!(wave.iter().any(|enemy| enemy.enemy_type == EnemyType::Boss));
+        assert!(create_wave(&SurvivalState::default(), 1, Difficulty::Normal).is_empty());
+    }
+}
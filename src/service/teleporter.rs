@@ -0,0 +1,220 @@
+use crate::{
+    constants::TELEPORTER_COOLDOWN,
+    math::check_circles_collide,
+    model::{
+        enemy::Enemy, projectile::Projectile, teleporter::Teleporter, Entity, GameEvent, Player,
+    },
+};
+
+fn is_on_pad(teleporter: &Teleporter, entity: &Entity) -> bool {
+    check_circles_collide(
+        teleporter.entity.position,
+        teleporter.entity.size,
+        entity.position,
+        entity.size,
+    )
+}
+
+/// Index of the first ready pad the entity stands on
+fn find_pad(
+    teleporters: &[Teleporter],
+    entity: &Entity,
+    can_use: impl Fn(&Teleporter) -> bool,
+) -> Option<usize> {
+    teleporters
+        .iter()
+        .position(|pad| pad.is_ready() && can_use(pad) && is_on_pad(pad, entity))
+}
+
+/// Moves the player, enemies and projectiles standing on ready pads to the paired pad.
+/// Both pads of a used pair go on cooldown so nothing bounces straight back.
+pub fn update_teleporters(
+    teleporters: Vec<Teleporter>,
+    player: &Player,
+    enemies: Vec<Enemy>,
+    projectiles: Vec<Projectile>,
+    delta: f32,
+) -> (Vec<Teleporter>, Vec<Enemy>, Vec<Projectile>, Vec<GameEvent>) {
+    let teleporters: Vec<_> = teleporters
+        .into_iter()
+        .map(|pad| Teleporter {
+            cooldown: (pad.cooldown - delta).max(0.0),
+            ..pad
+        })
+        .collect();
+    let mut used = vec![false; teleporters.len()];
+
+    let player_pad = find_pad(&teleporters, &player.entity, |pad| !pad.is_player_inside);
+    let player_position = match player_pad {
+        Some(index) => {
+            used[index] = true;
+            teleporters[index].destination
+        }
+        None => player.entity.position,
+    };
+    let events = player_pad
+        .map(|index| GameEvent::TeleportPlayer {
+            position: teleporters[index].destination,
+            look: teleporters[index].destination_look,
+        })
+        .into_iter()
+        .collect();
+
+    let enemies = enemies
+        .into_iter()
+        .map(
+            |enemy| match find_pad(&teleporters, &enemy.entity, |pad| pad.teleports_enemies) {
+                Some(index) => {
+                    used[index] = true;
+                    Enemy {
+                        entity: Entity {
+                            position: teleporters[index].destination,
+                            ..enemy.entity
+                        },
+                        ..enemy
+                    }
+                }
+                None => enemy,
+            },
+        )
+        .collect();
+
+    let projectiles = projectiles
+        .into_iter()
+        .map(|projectile| {
+            match find_pad(&teleporters, &projectile.entity, |pad| {
+                pad.teleports_projectiles
+            }) {
+                Some(index) => {
+                    used[index] = true;
+                    Projectile {
+                        entity: Entity {
+                            position: teleporters[index].destination,
+                            ..projectile.entity
+                        },
+                        ..projectile
+                    }
+                }
+                None => projectile,
+            }
+        })
+        .collect();
+
+    let used_destinations: Vec<_> = teleporters
+        .iter()
+        .zip(&used)
+        .filter(|(_, is_used)| **is_used)
+        .map(|(pad, _)| pad.destination)
+        .collect();
+    let player_entity = Entity {
+        position: player_position,
+        ..player.entity
+    };
+    let teleporters = teleporters
+        .into_iter()
+        .zip(used)
+        .map(|(pad, is_used)| {
+            let is_paired_with_used = used_destinations.contains(&pad.entity.position);
+            Teleporter {
+                is_player_inside: is_on_pad(&pad, &player_entity),
+                cooldown: if is_used || is_paired_with_used {
+                    TELEPORTER_COOLDOWN
+                } else {
+                    pad.cooldown
+                },
+                ..pad
+            }
+        })
+        .collect();
+
+    (teleporters, enemies, projectiles, events)
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::{vec2, Vec2};
+
+    use crate::model::{difficulty::Difficulty, enemy::EnemyType, TextureId};
+
+    use super::*;
+
+    fn create_pair(teleports_enemies: bool) -> Vec<Teleporter> {
+        let pad = |position: Vec2, destination: Vec2| Teleporter {
+            entity: Entity {
+                position,
+                size: 0.5,
+            },
+            destination,
+            destination_look: Some(vec2(1.0, 0.0)),
+            teleports_enemies,
+            teleports_projectiles: false,
+            cooldown: 0.0,
+            is_player_inside: false,
+        };
+        vec![
+            pad(vec2(0.0, 0.0), vec2(10.0, 0.0)),
+            pad(vec2(10.0, 0.0), vec2(0.0, 0.0)),
+        ]
+    }
+
+    #[test]
+    fn test_player_teleports_once() {
+        let (teleporters, _, _, events) = update_teleporters(
+            create_pair(false),
+            &Player::at(vec2(0.0, 0.0)),
+            vec![],
+            vec![],
+            0.1,
+        );
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            GameEvent::TeleportPlayer { position, look: Some(_) } if position == vec2(10.0, 0.0)
+        ));
+        assert!(teleporters.iter().all(|pad| !pad.is_ready()));
+        assert!(teleporters[1].is_player_inside);
+
+        // Standing on the destination pad doesn't send the player back, even after the cooldown
+        let (_, _, _, events) = update_teleporters(
+            teleporters,
+            &Player::at(vec2(10.0, 0.0)),
+            vec![],
+            vec![],
+            TELEPORTER_COOLDOWN + 0.1,
+        );
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_enemies_and_projectiles() {
+        let enemy = EnemyType::Melee.to_enemy(vec2(0.0, 0.0), Difficulty::Normal);
+        let projectile = Projectile {
+            entity: Entity {
+                position: vec2(10.0, 0.0),
+                size: 0.1,
+            },
+            direction: vec2(1.0, 0.0),
+            damage: 1.0,
+            texture: TextureId::Projectile,
+        };
+
+        let (_, enemies, projectiles, _) = update_teleporters(
+            create_pair(true),
+            &Player::at(vec2(5.0, 5.0)),
+            vec![enemy.clone()],
+            vec![projectile.clone()],
+            0.1,
+        );
+        assert_eq!(enemies[0].entity.position, vec2(10.0, 0.0));
+        assert_eq!(projectiles[0].entity.position, vec2(10.0, 0.0));
+
+        let (_, enemies, _, _) = update_teleporters(
+            create_pair(false),
+            &Player::at(vec2(5.0, 5.0)),
+            vec![enemy],
+            vec![],
+            0.1,
+        );
+        assert_eq!(enemies[0].entity.position, vec2(0.0, 0.0));
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{
+    math::check_circles_collide,
+    model::{
+        trigger::{Trigger, TriggerAction},
+        GameEvent, Message, Player,
+    },
+};
+
+fn action_to_event(action: &TriggerAction) -> GameEvent {
+    match action {
+        TriggerAction::SpawnEnemy {
+            enemy_type,
+            position,
+        } => GameEvent::SpawnEnemy {
+            position: *position,
+            enemy_type: *enemy_type,
+        },
+        TriggerAction::OpenDoor(name) => GameEvent::OpenDoor(name.clone()),
+        TriggerAction::CloseDoor(name) => GameEvent::CloseDoor(name.clone()),
+        TriggerAction::ShowMessage { text, duration } => GameEvent::ShowMessage {
+            text: text.clone(),
+            duration: *duration,
+        },
+        TriggerAction::PlaySound(sound) => GameEvent::PlaySound(*sound),
+        TriggerAction::ChangeLighting(light_level) => GameEvent::ChangeLighting(*light_level),
+        TriggerAction::ChangeFog(fog) => GameEvent::ChangeFog(*fog),
+        TriggerAction::Teleport(position) => GameEvent::TeleportPlayer {
+            position: *position,
+            look: None,
+        },
+        TriggerAction::EndLevel => GameEvent::EndLevel,
+    }
+}
+
+/// Fires the trigger when the player enters it, staying inside doesn't fire it again.
+fn update_trigger(player: &Player, trigger: Trigger) -> (Trigger, Vec<GameEvent>) {
+    let is_player_inside = check_circles_collide(
+        trigger.entity.position,
+        trigger.entity.size,
+        player.entity.position,
+        player.entity.size,
+    );
+    let fires =
+        is_player_inside && !trigger.is_player_inside && !(trigger.once && trigger.has_fired);
+
+    let events = if fires {
+        trigger
+            .actions
+            .iter()
+            .map(action_to_event)
+            .chain(trigger.name.clone().map(GameEvent::TriggerFired))
+            .collect()
+    } else {
+        vec![]
+    };
+
+    (
+        Trigger {
+            is_player_inside,
+            has_fired: trigger.has_fired || fires,
+            ..trigger
+        },
+        events,
+    )
+}
+
+pub fn update_triggers(player: &Player, triggers: Vec<Trigger>) -> (Vec<Trigger>, Vec<GameEvent>) {
+    let (triggers, events): (Vec<_>, Vec<_>) = triggers
+        .into_iter()
+        .map(|trigger| update_trigger(player, trigger))
+        .unzip();
+
+    (triggers, events.into_iter().flatten().collect())
+}
+
+pub fn update_message(message: Option<Message>, delta: f32) -> Option<Message> {
+    message
+        .map(|message| Message {
+            time_left: message.time_left - delta,
+            ..message
+        })
+        .filter(|message| message.time_left > 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::vec2;
+
+    use crate::model::Entity;
+
+    use super::*;
+
+    #[test]
+    fn test_update_triggers() {
+        let trigger = Trigger {
+            name: None,
+            entity: Entity {
+                position: vec2(0.0, 0.0),
+                size: 1.0,
+            },
+            actions: vec![
+                TriggerAction::OpenDoor("gate".to_string()),
+                TriggerAction::EndLevel,
+            ],
+            once: true,
+            has_fired: false,
+            is_player_inside: false,
+        };
+
+        let (triggers, events) = update_triggers(&Player::at(vec2(5.0, 0.0)), vec![trigger]);
+        assert!(events.is_empty());
+
+        let (triggers, events) = update_triggers(&Player::at(vec2(0.0, 0.0)), triggers);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], GameEvent::OpenDoor(name) if name == "gate"));
+
+        let (triggers, events) = update_triggers(&Player::at(vec2(0.1, 0.0)), triggers);
+        assert!(events.is_empty());
+
+        let (triggers, _) = update_triggers(&Player::at(vec2(5.0, 0.0)), triggers);
+        let (_, events) = update_triggers(&Player::at(vec2(0.0, 0.0)), triggers);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_update_message() {
+        let message = Some(Message {
+            text: "Hello".to_string(),
+            time_left: 1.0,
+        });
+
+        let message = update_message(message, 0.5);
+        assert!(message.is_some());
+        assert!(update_message(message, 0.6).is_none());
+    }
+}